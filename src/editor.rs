@@ -1,15 +1,32 @@
 mod terminal;
 mod commands;
 mod filestatus;
+mod lineending;
+mod encoding;
+mod rawbyte;
 mod uielements;
 mod line;
 mod position;
 mod size;
 mod annotatedstring;
+mod clipboard;
+mod datadirectory;
+mod macros;
+mod globalmarks;
+mod localhistory;
+mod config;
 
 use std::{
-    io::Error,
+    cmp::min,
+    io::{
+        Error,
+        ErrorKind,
+    },
     env,
+    path::{
+        Path,
+        PathBuf,
+    },
     panic::{
         set_hook,
         take_hook,
@@ -24,9 +41,14 @@ use crossterm::event::{
 use terminal::Terminal;
 use commands::{
     Command,
+    Transform,
+    LineOperation,
+    KeyBinding,
     Command::{
         Edit,
         Move,
+        Select,
+        SelectBlock,
         System,
     },
     System::{
@@ -35,6 +57,39 @@ use commands::{
         Save,
         Dismiss,
         Search,
+        Copy,
+        Cut,
+        Paste,
+        KillLine,
+        KillWordForward,
+        KillWordBackward,
+        YankPop,
+        CopyToRegister,
+        PasteFromRegister,
+        ToggleSubwordMode,
+        GotoLine,
+        AddCursorAbove,
+        AddCursorBelow,
+        AddCursorAtNextMatch,
+        AddCursorsAtAllMatches,
+        ToggleMacroRecording,
+        PlayMacro,
+        PlayMacroRepeatedly,
+        SaveMacro,
+        LoadMacro,
+        ToggleAutoIndent,
+        ToggleOverwriteMode,
+        TransformSelection,
+        SortLines,
+        JumpBack,
+        JumpForward,
+        PreviousChange,
+        NextChange,
+        SetMark,
+        JumpToMark,
+        SetLineEnding,
+        SetEncoding,
+        ShowHistory,
     },
     Move::{
         Up,
@@ -42,10 +97,21 @@ use commands::{
         Left,
         Right,
     },
-    Edit::InsertLine,
+    Edit::{
+        InsertLine,
+        InsertCharacter,
+        Increment,
+        IncrementSequence,
+    },
 };
 use filestatus::FileStatus;
-use line::Line;
+use lineending::LineEnding;
+use encoding::Encoding;
+use rawbyte::RawByte;
+use line::{
+    Line,
+    Highlight,
+};
 use position::{
     Position,
     Row,
@@ -56,28 +122,73 @@ use annotatedstring::{
     AnnotatedString,
     AnnotationType,
 };
+use clipboard::{
+    Clipboard,
+    ClipboardEntry,
+};
+use datadirectory::DataDirectory;
+use macros::{
+    Macros,
+    MAX_MACRO_REPEAT_COUNT,
+};
+use globalmarks::GlobalMarks;
+use localhistory::{
+    LocalHistory,
+    HistoryVersion,
+    DiffLine,
+    LineDiff,
+};
+use config::Config;
 use uielements::{
     UIElement,
     View,
+    Location,
     StatusBar,
     HintBar,
     CommandBar,
+    Picker,
+    PickerItem,
 };
 
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+const DIFF_CONTEXT_LINES: usize = 2;
 
-#[derive(Default, Eq, PartialEq)]
+#[derive(Default, Copy, Clone, Eq, PartialEq)]
 enum PromptType {
     #[default]
     None,
     Search,
     Save,
+    GotoLine,
+    CopyToRegister,
+    PasteFromRegister,
+    PlayMacroRepeatedly,
+    SaveMacro,
+    LoadMacro,
+    Transform,
+    LineOperation,
+    SortByColumn,
+    SetMark,
+    JumpToMark,
+    LineEnding,
+    Encoding,
+    History,
+    HistoryVersion,
+    HistoryDiff,
 }
 
 impl PromptType {
     fn is_none(&self) -> bool {
         return *self == Self::None;
     }
+
+    fn has_picker(&self) -> bool {
+        return matches!(self, Self::CopyToRegister | Self::PasteFromRegister | Self::LoadMacro | Self::Transform | Self::LineOperation | Self::SetMark | Self::JumpToMark | Self::LineEnding | Self::Encoding | Self::History | Self::HistoryVersion | Self::HistoryDiff);
+    }
+
+    fn is_macro_prompt(&self) -> bool {
+        return matches!(self, Self::PlayMacroRepeatedly | Self::SaveMacro | Self::LoadMacro);
+    }
 }
 
 #[derive(Default)]
@@ -87,7 +198,14 @@ pub struct Editor {
     statusbar: StatusBar,
     hintbar: HintBar,
     commandbar: CommandBar,
+    picker: Picker,
+    clipboard: Clipboard,
+    macros: Macros,
+    local_history: LocalHistory,
+    selected_version: Option<HistoryVersion>,
+    key_bindings: Vec<(KeyBinding, Command)>,
     prompt_type: PromptType,
+    previous_command: Option<Command>,
     title: String,
     terminal_size: Size,
 }
@@ -108,14 +226,23 @@ impl Editor {
 
         Terminal::init()?;
 
+        let config = Config::load();
         let mut editor = Self::default();
+
+        editor.view.apply_config(&config);
+        editor.local_history = LocalHistory::new(config.history_versions, config.history_days);
+        editor.key_bindings = vec![
+            (config.increment_key, Edit(Increment(1))),
+            (config.decrement_key, Edit(Increment(-1))),
+            (config.increment_sequence_key, Edit(IncrementSequence(1))),
+        ];
         editor.handle_resize_command(Terminal::size().unwrap_or_default());
         editor.update_hint("[ Control + F -> Search ] [ Control + S -> Save ] [ Control + Q -> Quit ]");
 
         let args: Vec<String> = env::args().collect();
 
         if let Some(file) = args.get(1) {
-            if editor.view.load(file).is_err() {
+            if !editor.load_file(file) {
                 editor.update_hint("[ Error opening the file ]");
             }
         }
@@ -147,6 +274,12 @@ impl Editor {
     }
 
     fn eval_event(&mut self, event: Event) {
+        if let Event::Paste(text) = &event {
+            self.handle_paste_event(text);
+
+            return;
+        }
+
         let should_process = match &event {
             Event::Key(
                 KeyEvent {
@@ -165,12 +298,34 @@ impl Editor {
         };
 
         if should_process {
-            if let Ok(command) = Command::try_from(event) {
+            if let Some(command) = self.get_bound_command(&event) {
+                self.process_command(command);
+            } else if let Ok(command) = Command::try_from(event) {
                 self.process_command(command);
             }
         }
     }
 
+    fn get_bound_command(&self, event: &Event) -> Option<Command> {
+        let Event::Key(key_event) = event else {
+            return None;
+        };
+
+        return self
+            .key_bindings
+            .iter()
+            .find(
+                |(key_binding, _)| {
+                    return key_binding.matches(key_event);
+                }
+            )
+            .map(
+                |(_, command)| {
+                    return *command;
+                }
+            );
+    }
+
     fn process_command(&mut self, command: Command) {
         if let System(Resize(size)) = command {
             self.handle_resize_command(size);
@@ -178,6 +333,10 @@ impl Editor {
             return;
         }
 
+        if self.should_record(command) {
+            self.macros.record(command);
+        }
+
         match self.prompt_type {
             PromptType::Search => {
                 self.process_search_command(command);
@@ -185,10 +344,38 @@ impl Editor {
             PromptType::Save => {
                 self.process_save_command(command);
             },
+            PromptType::GotoLine => {
+                self.process_goto_line_command(command);
+            },
+            PromptType::PlayMacroRepeatedly => {
+                self.process_play_macro_command(command);
+            },
+            PromptType::SaveMacro => {
+                self.process_save_macro_command(command);
+            },
+            PromptType::SortByColumn => {
+                self.process_sort_by_column_command(command);
+            },
+            PromptType::CopyToRegister
+            | PromptType::PasteFromRegister
+            | PromptType::LoadMacro
+            | PromptType::Transform
+            | PromptType::LineOperation
+            | PromptType::SetMark
+            | PromptType::JumpToMark
+            | PromptType::LineEnding
+            | PromptType::Encoding
+            | PromptType::History
+            | PromptType::HistoryVersion
+            | PromptType::HistoryDiff => {
+                self.process_picker_command(command);
+            },
             PromptType::None => {
                 self.process_no_prompt_command(command);
             },
         }
+
+        self.previous_command = Some(command);
     }
 
     fn process_search_command(&mut self, command: Command) {
@@ -203,6 +390,16 @@ impl Editor {
                 self.view.exit_search();
                 self.update_hint("[ Done searching ]");
             },
+            System(AddCursorsAtAllMatches) => {
+                let query = self.commandbar.get_value();
+
+                self.set_prompt(PromptType::None);
+                self.view.exit_search();
+
+                let cursors_count = self.view.select_all_matches(&query);
+
+                self.update_cursors_count_hint(cursors_count);
+            },
             Edit(edit_command) => {
                 self.commandbar.handle_edit_command(edit_command);
 
@@ -216,25 +413,15 @@ impl Editor {
             Move(Down | Right) => {
                 self.view.search_next();
             },
-            System(
-                Quit
-                | Resize(_)
-                | Search
-                | Save
-            )
-            | Move(_) => {},
+            System(_)
+            | Move(_)
+            | Select(_)
+            | SelectBlock(_) => {},
         }
     }
 
     fn process_save_command(&mut self, command: Command) {
         match command {
-            System(
-                Quit
-                | Resize(_)
-                | Search
-                | Save
-            )
-            | Move(_) => {},
             System(Dismiss) => {
                 self.set_prompt(PromptType::None);
                 self.update_hint("[ Cancelled saving ]");
@@ -247,6 +434,240 @@ impl Editor {
             Edit(edit_command) => {
                 self.commandbar.handle_edit_command(edit_command);
             },
+            System(_)
+            | Move(_)
+            | Select(_)
+            | SelectBlock(_) => {},
+        }
+    }
+
+    fn process_goto_line_command(&mut self, command: Command) {
+        match command {
+            System(Dismiss) => {
+                self.set_prompt(PromptType::None);
+                self.update_hint("[ Cancelled going to line ]");
+            },
+            Edit(InsertLine) => {
+                let query = self.commandbar.get_value();
+
+                self.set_prompt(PromptType::None);
+
+                if !self.view.go_to(&query) {
+                    self.update_hint("[ Invalid line number ]");
+                }
+            },
+            Edit(edit_command) => {
+                self.commandbar.handle_edit_command(edit_command);
+            },
+            System(_)
+            | Move(_)
+            | Select(_)
+            | SelectBlock(_) => {},
+        }
+    }
+
+    fn should_record(&self, command: Command) -> bool {
+        if matches!(command, System(system_command) if system_command.is_macro_command()) {
+            return false;
+        }
+
+        return !self.prompt_type.is_macro_prompt();
+    }
+
+    fn process_play_macro_command(&mut self, command: Command) {
+        match command {
+            System(Dismiss) => {
+                self.set_prompt(PromptType::None);
+                self.update_hint("[ Cancelled playing the macro ]");
+            },
+            Edit(InsertLine) => {
+                let times = self.commandbar.get_value();
+
+                self.set_prompt(PromptType::None);
+
+                match times.trim().parse::<usize>() {
+                    Ok(times) if times > MAX_MACRO_REPEAT_COUNT => {
+                        self.update_hint(&format!("[ Playing the macro at most {MAX_MACRO_REPEAT_COUNT} times ]"));
+                        self.play_macro(MAX_MACRO_REPEAT_COUNT);
+                    },
+                    Ok(times) if times > 0 => {
+                        self.play_macro(times);
+                    },
+                    _ => {
+                        self.update_hint("[ Invalid repeat count ]");
+                    },
+                }
+            },
+            Edit(edit_command) => {
+                self.commandbar.handle_edit_command(edit_command);
+            },
+            System(_)
+            | Move(_)
+            | Select(_)
+            | SelectBlock(_) => {},
+        }
+    }
+
+    fn process_save_macro_command(&mut self, command: Command) {
+        match command {
+            System(Dismiss) => {
+                self.set_prompt(PromptType::None);
+                self.update_hint("[ Cancelled saving the macro ]");
+            },
+            Edit(InsertLine) => {
+                let name = self.commandbar.get_value();
+                let name = name.trim();
+
+                self.set_prompt(PromptType::None);
+
+                if name.is_empty() || name.contains('\t') {
+                    self.update_hint("[ Invalid macro name ]");
+                } else if self.macros.save(name).is_ok() {
+                    self.update_hint(&format!("[ Saved macro {name} ]"));
+                } else {
+                    self.update_hint("[ Error saving the macro ]");
+                }
+            },
+            Edit(edit_command) => {
+                self.commandbar.handle_edit_command(edit_command);
+            },
+            System(_)
+            | Move(_)
+            | Select(_)
+            | SelectBlock(_) => {},
+        }
+    }
+
+    fn process_sort_by_column_command(&mut self, command: Command) {
+        match command {
+            System(Dismiss) => {
+                self.set_prompt(PromptType::None);
+                self.update_hint("[ Cancelled sorting ]");
+            },
+            Edit(InsertLine) => {
+                let value = self.commandbar.get_value();
+
+                self.set_prompt(PromptType::None);
+
+                if let Some(operation) = LineOperation::parse_column(&value) {
+                    self.view.handle_edit_command(commands::Edit::LineOperation(operation));
+                } else {
+                    self.update_hint("[ Invalid column ]");
+                }
+            },
+            Edit(edit_command) => {
+                self.commandbar.handle_edit_command(edit_command);
+            },
+            System(_)
+            | Move(_)
+            | Select(_)
+            | SelectBlock(_) => {},
+        }
+    }
+
+    fn process_picker_command(&mut self, command: Command) {
+        match command {
+            System(Dismiss) => {
+                self.set_prompt(PromptType::None);
+                self.update_hint("[ Cancelled ]");
+            },
+            Move(Up) => {
+                self.picker.select_previous();
+            },
+            Move(Down) => {
+                self.picker.select_next();
+            },
+            Edit(InsertLine) => {
+                if let Some(key) = self.picker.get_selected().map(|item| { return item.key; }) {
+                    self.handle_pick(key);
+                }
+            },
+            Edit(InsertCharacter(key)) => {
+                if self.picker.get_by_key(key).is_some() {
+                    self.handle_pick(key);
+                }
+            },
+            System(_)
+            | Edit(_)
+            | Move(_)
+            | Select(_)
+            | SelectBlock(_) => {},
+        }
+    }
+
+    fn handle_pick(&mut self, key: char) {
+        let prompt_type = self.prompt_type;
+        let label = self.picker.get_by_key(key).map(|item| { return item.label.clone(); }).unwrap_or_default();
+
+        self.set_prompt(PromptType::None);
+
+        match prompt_type {
+            PromptType::CopyToRegister => {
+                self.view.copy_to_register(key);
+                self.update_hint(&format!("[ Copied to register {key} ]"));
+            },
+            PromptType::PasteFromRegister => {
+                self.view.paste_from_register(key);
+            },
+            PromptType::LoadMacro => {
+                match self.macros.load(&label) {
+                    Ok(()) => {
+                        self.update_hint(&format!("[ Loaded macro {label} ]"));
+                    },
+                    Err(error) if error.kind() == ErrorKind::InvalidData => {
+                        self.update_hint(&format!("[ Macro {label} uses an unknown command ]"));
+                    },
+                    Err(_) => {
+                        self.update_hint("[ Error loading the macro ]");
+                    },
+                }
+            },
+            PromptType::Transform => {
+                let result = Transform::from_key(key).map(
+                    |transform| {
+                        return self.view.transform(transform);
+                    }
+                );
+
+                if let Some(Err(error)) = result {
+                    self.update_hint(&format!("[ {error} ]"));
+                }
+            },
+            PromptType::LineOperation => {
+                if key == 'c' {
+                    self.set_prompt(PromptType::SortByColumn);
+                } else if let Some(operation) = LineOperation::from_key(key) {
+                    self.view.handle_edit_command(commands::Edit::LineOperation(operation));
+                }
+            },
+            PromptType::SetMark => {
+                self.handle_set_mark(key);
+            },
+            PromptType::JumpToMark => {
+                self.handle_jump_to_mark(key);
+            },
+            PromptType::LineEnding => {
+                self.handle_line_ending_pick(key);
+            },
+            PromptType::Encoding => {
+                self.handle_encoding_pick(key);
+            },
+            PromptType::History => {
+                self.handle_history_pick(key);
+            },
+            PromptType::HistoryVersion => {
+                self.handle_history_version_pick(key);
+            },
+            PromptType::HistoryDiff => {
+                self.set_prompt(PromptType::HistoryVersion);
+            },
+            PromptType::Search
+            | PromptType::Save
+            | PromptType::GotoLine
+            | PromptType::PlayMacroRepeatedly
+            | PromptType::SaveMacro
+            | PromptType::SortByColumn
+            | PromptType::None => {},
         }
     }
 
@@ -260,20 +681,180 @@ impl Editor {
             System(
                 Quit
                 | Resize(_)
-                | Dismiss
             ) => {},
+            System(Dismiss) => {
+                self.view.dismiss();
+            },
             System(Search) => {
                 self.set_prompt(PromptType::Search);
             },
             System(Save) => {
                 self.handle_save_command();
             },
+            System(Copy) => {
+                self.handle_copy_command();
+            },
+            System(Cut) => {
+                self.handle_cut_command();
+            },
+            System(Paste) => {
+                self.handle_paste_command();
+            },
+            System(KillLine) => {
+                let entry = self.view.kill_line(self.is_kill_sequence());
+
+                self.copy_to_clipboard(entry, "[ Killed ]");
+            },
+            System(KillWordForward) => {
+                let entry = self.view.kill_word_forward(self.is_kill_sequence());
+
+                self.copy_to_clipboard(entry, "[ Killed ]");
+            },
+            System(KillWordBackward) => {
+                let entry = self.view.kill_word_backward(self.is_kill_sequence());
+
+                self.copy_to_clipboard(entry, "[ Killed ]");
+            },
+            System(YankPop) => {
+                self.handle_yank_pop_command();
+            },
+            System(CopyToRegister) => {
+                self.set_prompt(PromptType::CopyToRegister);
+            },
+            System(PasteFromRegister) => {
+                self.handle_paste_from_register_command();
+            },
+            System(GotoLine) => {
+                self.set_prompt(PromptType::GotoLine);
+            },
+            System(AddCursorAbove) => {
+                self.view.add_cursor_above();
+            },
+            System(AddCursorBelow) => {
+                self.view.add_cursor_below();
+            },
+            System(AddCursorAtNextMatch) => {
+                if !self.view.add_cursor_at_next_match() {
+                    self.update_hint("[ No more occurrences ]");
+                }
+            },
+            System(AddCursorsAtAllMatches) => {
+                let cursors_count = self.view.select_all_occurrences();
+
+                self.update_cursors_count_hint(cursors_count);
+            },
+            System(ToggleMacroRecording) => {
+                if self.macros.is_recording() {
+                    let commands_count = self.macros.stop_recording();
+
+                    self.update_hint(&format!("[ Recorded a macro of {commands_count} commands ]"));
+                } else {
+                    self.macros.start_recording();
+                    self.update_hint("[ Recording a macro ]");
+                }
+            },
+            System(PlayMacro) => {
+                self.play_macro(1);
+            },
+            System(PlayMacroRepeatedly) => {
+                if self.macros.get_current().is_empty() {
+                    self.update_hint("[ No macro recorded ]");
+                } else {
+                    self.set_prompt(PromptType::PlayMacroRepeatedly);
+                }
+            },
+            System(SaveMacro) => {
+                if self.macros.get_current().is_empty() {
+                    self.update_hint("[ No macro recorded ]");
+                } else {
+                    self.set_prompt(PromptType::SaveMacro);
+                }
+            },
+            System(LoadMacro) => {
+                self.handle_load_macro_command();
+            },
+            System(ToggleAutoIndent) => {
+                if self.view.toggle_auto_indent() {
+                    self.update_hint("[ Auto-indent enabled ]");
+                } else {
+                    self.update_hint("[ Auto-indent disabled ]");
+                }
+            },
+            System(TransformSelection) => {
+                self.set_prompt(PromptType::Transform);
+            },
+            System(SortLines) => {
+                self.set_prompt(PromptType::LineOperation);
+            },
+            System(SetMark) => {
+                self.set_prompt(PromptType::SetMark);
+            },
+            System(JumpToMark) => {
+                if self.get_mark_items(false).is_empty() {
+                    self.update_hint("[ No marks set ]");
+                } else {
+                    self.set_prompt(PromptType::JumpToMark);
+                }
+            },
+            System(SetLineEnding) => {
+                self.set_prompt(PromptType::LineEnding);
+            },
+            System(SetEncoding) => {
+                self.set_prompt(PromptType::Encoding);
+            },
+            System(ShowHistory) => {
+                if self.get_history_items().is_empty() {
+                    self.update_hint("[ No earlier versions of this file ]");
+                } else {
+                    self.set_prompt(PromptType::History);
+                }
+            },
+            System(JumpBack) => {
+                if !self.view.jump_back() {
+                    self.update_hint("[ No earlier jump ]");
+                }
+            },
+            System(JumpForward) => {
+                if !self.view.jump_forward() {
+                    self.update_hint("[ No later jump ]");
+                }
+            },
+            System(PreviousChange) => {
+                if !self.view.go_to_previous_change() {
+                    self.update_hint("[ No earlier change ]");
+                }
+            },
+            System(NextChange) => {
+                if !self.view.go_to_next_change() {
+                    self.update_hint("[ No later change ]");
+                }
+            },
+            System(ToggleOverwriteMode) => {
+                if self.view.toggle_overwrite_mode() {
+                    self.update_hint("[ Overwrite mode ]");
+                } else {
+                    self.update_hint("[ Insert mode ]");
+                }
+            },
+            System(ToggleSubwordMode) => {
+                if self.view.toggle_subword_mode() {
+                    self.update_hint("[ Subword motion enabled ]");
+                } else {
+                    self.update_hint("[ Subword motion disabled ]");
+                }
+            },
             Edit(edit_command) => {
                 self.view.handle_edit_command(edit_command);
             },
             Move(move_command) => {
                 self.view.handle_move_command(move_command);
             },
+            Select(move_command) => {
+                self.view.handle_select_command(move_command);
+            },
+            SelectBlock(move_command) => {
+                self.view.handle_select_block_command(move_command);
+            },
         }
     }
 
@@ -290,6 +871,12 @@ impl Editor {
                 height: size.height.saturating_sub(2),
             }
         );
+        self.picker.resize(
+            Size {
+                width: size.width,
+                height: size.height.saturating_sub(2).div_ceil(2),
+            }
+        );
         self.hintbar.resize(bar_size);
         self.statusbar.resize(bar_size);
         self.commandbar.resize(bar_size);
@@ -313,18 +900,586 @@ impl Editor {
         }
     }
 
+    fn handle_copy_command(&mut self) {
+        let entry = self.view.copy();
+
+        self.copy_to_clipboard(entry, "[ Copied ]");
+    }
+
+    fn handle_cut_command(&mut self) {
+        let entry = self.view.cut();
+
+        self.copy_to_clipboard(entry, "[ Cut ]");
+    }
+
+    fn copy_to_clipboard(&mut self, entry: ClipboardEntry, success_hint: &str) {
+        if self.clipboard.copy(entry).is_ok() {
+            self.update_hint(success_hint);
+        } else {
+            self.update_hint("[ Error copying to the system clipboard ]");
+        }
+    }
+
+    fn handle_paste_command(&mut self) {
+        let entry = self.clipboard.paste();
+
+        self.view.paste(&entry);
+    }
+
+    fn handle_paste_event(&mut self, text: &str) {
+        if self.is_in_prompt() {
+            for character in text.chars().filter(|character| { return !character.is_control(); }) {
+                self.process_command(Edit(InsertCharacter(character)));
+            }
+        } else {
+            self.view.paste(&ClipboardEntry::from(text));
+        }
+    }
+
+    fn play_macro(&mut self, times: usize) {
+        if self.macros.is_playing() {
+            return;
+        }
+
+        if self.macros.is_recording() {
+            self.update_hint("[ Stop recording before playing the macro ]");
+
+            return;
+        }
+
+        let commands = self.macros.get_current().to_vec();
+
+        if commands.is_empty() {
+            self.update_hint("[ No macro recorded ]");
+
+            return;
+        }
+
+        self.macros.set_playing(true);
+
+        'playback: for _ in 0..times {
+            for command in &commands {
+                if self.should_quit {
+                    break 'playback;
+                }
+
+                self.process_command(*command);
+            }
+        }
+
+        self.macros.set_playing(false);
+    }
+
+    fn handle_load_macro_command(&mut self) {
+        match Macros::get_saved_names() {
+            Ok(names) if !names.is_empty() => {
+                self.set_prompt(PromptType::LoadMacro);
+            },
+            Ok(_) => {
+                self.update_hint("[ No saved macros ]");
+            },
+            Err(_) => {
+                self.update_hint("[ Error reading the saved macros ]");
+            },
+        }
+    }
+
+    fn get_macro_items() -> Vec<PickerItem> {
+        return ('1'..='9')
+            .chain('a'..='z')
+            .zip(Macros::get_saved_names().unwrap_or_default())
+            .map(
+                |(key, label)| {
+                    return PickerItem {
+                        key,
+                        label,
+                    };
+                }
+            )
+            .collect();
+    }
+
+    fn get_transform_items() -> Vec<PickerItem> {
+        return Transform::ALL
+            .into_iter()
+            .map(
+                |transform| {
+                    return PickerItem {
+                        key: transform.get_key(),
+                        label: String::from(transform.get_label()),
+                    };
+                }
+            )
+            .collect();
+    }
+
+    fn get_line_operation_items() -> Vec<PickerItem> {
+        return LineOperation::PICKER_ITEMS
+            .into_iter()
+            .map(
+                |(key, label)| {
+                    return PickerItem {
+                        key,
+                        label: String::from(label),
+                    };
+                }
+            )
+            .collect();
+    }
+
+    fn get_line_ending_items() -> Vec<PickerItem> {
+        return [
+            ('l', "Convert to LF (Unix)"),
+            ('c', "Convert to CRLF (Windows)"),
+            ('n', "Add / remove the final newline"),
+        ]
+            .into_iter()
+            .map(
+                |(key, label)| {
+                    return PickerItem {
+                        key,
+                        label: String::from(label),
+                    };
+                }
+            )
+            .collect();
+    }
+
+    fn handle_line_ending_pick(&mut self, key: char) {
+        let line_ending = match key {
+            'l' => {
+                LineEnding::Lf
+            },
+            'c' => {
+                LineEnding::Crlf
+            },
+            _ => {
+                if self.view.toggle_final_newline() {
+                    self.update_hint("[ Added the final newline ]");
+                } else {
+                    self.update_hint("[ Removed the final newline ]");
+                }
+
+                return;
+            },
+        };
+
+        if self.view.set_line_ending(line_ending) {
+            self.update_hint(&format!("[ Converted to {line_ending} ]"));
+        } else {
+            self.update_hint(&format!("[ Already {line_ending} ]"));
+        }
+    }
+
+    fn get_encoding_items() -> Vec<PickerItem> {
+        let save_items = Encoding::ALL.into_iter().map(
+            |encoding| {
+                return PickerItem {
+                    key: encoding.get_key(),
+                    label: format!("Save as {encoding}"),
+                };
+            }
+        );
+        let reopen_items = Encoding::ALL.into_iter().map(
+            |encoding| {
+                return PickerItem {
+                    key: encoding.get_key().to_ascii_uppercase(),
+                    label: format!("Reopen as {encoding}"),
+                };
+            }
+        );
+
+        return save_items.chain(reopen_items).collect();
+    }
+
+    fn handle_encoding_pick(&mut self, key: char) {
+        let Some(encoding) = Encoding::from_key(key) else {
+            return;
+        };
+
+        if !key.is_ascii_uppercase() {
+            if self.view.set_encoding(encoding) {
+                self.update_hint(&format!("[ Will save as {encoding} ]"));
+            } else {
+                self.update_hint(&format!("[ Already {encoding} ]"));
+            }
+        } else if self.view.get_current_status().modified {
+            self.update_hint("[ Save the file before reopening it ]");
+        } else if self.view.reopen_as(encoding).is_ok() {
+            self.update_hint(&format!("[ Reopened as {encoding} ]"));
+        } else {
+            self.update_hint("[ Error opening the file ]");
+        }
+    }
+
+    fn get_history_keys() -> impl Iterator<Item = char> {
+        return ('1'..='9').chain('a'..='z').chain('A'..='Z');
+    }
+
+    fn get_history_versions(&self) -> Vec<HistoryVersion> {
+        return self.view
+            .get_file_path()
+            .and_then(
+                |file_path| {
+                    return LocalHistory::get_versions(file_path).ok();
+                }
+            )
+            .unwrap_or_default();
+    }
+
+    fn get_history_items(&self) -> Vec<PickerItem> {
+        return Self::get_history_keys()
+            .zip(self.get_history_versions())
+            .map(
+                |(key, version)| {
+                    return PickerItem {
+                        key,
+                        label: format!(
+                            "{}  {} bytes",
+                            version.get_timestamp(),
+                            version.size,
+                        ),
+                    };
+                }
+            )
+            .collect();
+    }
+
+    fn handle_history_pick(&mut self, key: char) {
+        let version = Self::get_history_keys()
+            .position(
+                |history_key| {
+                    return history_key == key;
+                }
+            )
+            .and_then(
+                |index| {
+                    return self.get_history_versions().into_iter().nth(index);
+                }
+            );
+
+        if version.is_some() {
+            self.selected_version = version;
+            self.set_prompt(PromptType::HistoryVersion);
+        }
+    }
+
+    fn get_history_version_items() -> Vec<PickerItem> {
+        return [
+            ('d', "Diff against the buffer"),
+            ('r', "Restore into the buffer"),
+        ]
+            .into_iter()
+            .map(
+                |(key, label)| {
+                    return PickerItem {
+                        key,
+                        label: String::from(label),
+                    };
+                }
+            )
+            .collect();
+    }
+
+    fn handle_history_version_pick(&mut self, key: char) {
+        let Some(version) = self.selected_version.clone() else {
+            return;
+        };
+
+        if key == 'd' {
+            match self.get_history_diff_items() {
+                Ok(items) if items.is_empty() => {
+                    self.set_prompt(PromptType::HistoryVersion);
+                    self.update_hint("[ The version matches the buffer ]");
+                },
+                Ok(_) => {
+                    self.set_prompt(PromptType::HistoryDiff);
+                },
+                Err(_) => {
+                    self.update_hint("[ Error reading the version ]");
+                },
+            }
+        } else if let Ok(lines) = self.view.read_lines(&version.path) {
+            self.view.replace_all_lines(&lines);
+            self.update_hint(&format!("[ Restored the version from {}. Undo to go back ]", version.get_timestamp()));
+        } else {
+            self.update_hint("[ Error reading the version ]");
+        }
+    }
+
+    fn get_history_diff_items(&self) -> Result<Vec<PickerItem>, Error> {
+        let Some(version) = &self.selected_version else {
+            return Ok(Vec::new());
+        };
+        let diff = LineDiff::diff(&self.view.read_lines(&version.path)?, &self.view.get_all_lines());
+        let mut items = Vec::new();
+        let mut line_number: usize = 0;
+        let mut last_shown_index = None;
+
+        for (index, diff_line) in diff.iter().enumerate() {
+            if !matches!(diff_line, DiffLine::Removed(_)) {
+                line_number = line_number.saturating_add(1);
+            }
+
+            let context = &diff[index.saturating_sub(DIFF_CONTEXT_LINES)..min(index.saturating_add(DIFF_CONTEXT_LINES).saturating_add(1), diff.len())];
+
+            if !context.iter().any(|line| { return !matches!(line, DiffLine::Kept(_)); }) {
+                continue;
+            }
+
+            if last_shown_index.is_none_or(|last_index: usize| { return last_index.saturating_add(1) < index; }) {
+                let hunk_line_number = if matches!(diff_line, DiffLine::Removed(_)) {
+                    line_number.saturating_add(1)
+                } else {
+                    line_number
+                };
+
+                items.push(
+                    PickerItem {
+                        key: '@',
+                        label: format!("Line {hunk_line_number}"),
+                    }
+                );
+            }
+
+            items.push(
+                PickerItem {
+                    key: diff_line.get_key(),
+                    label: String::from(diff_line.get_text()),
+                }
+            );
+            last_shown_index = Some(index);
+        }
+
+        return Ok(items);
+    }
+
+    fn update_cursors_count_hint(&mut self, cursors_count: usize) {
+        if cursors_count == 0 {
+            self.update_hint("[ No matches ]");
+        } else {
+            self.update_hint(&format!("[ {cursors_count} cursors ]"));
+        }
+    }
+
+    fn is_kill_sequence(&self) -> bool {
+        return matches!(
+            self.previous_command,
+            Some(System(KillLine | KillWordForward | KillWordBackward))
+        );
+    }
+
+    fn handle_yank_pop_command(&mut self) {
+        let follows_paste = matches!(
+            self.previous_command,
+            Some(System(Paste | YankPop))
+        );
+
+        if !follows_paste || !self.view.yank_pop() {
+            self.update_hint("[ Yank pop only works right after a paste ]");
+        }
+    }
+
+    fn handle_paste_from_register_command(&mut self) {
+        if self.get_register_items(false).is_empty() {
+            self.update_hint("[ All registers are empty ]");
+        } else {
+            self.set_prompt(PromptType::PasteFromRegister);
+        }
+    }
+
+    fn get_register_items(&self, include_empty: bool) -> Vec<PickerItem> {
+        let registers = self.view.get_registers();
+        let names: Vec<char> = if include_empty {
+            ('a'..='z').collect()
+        } else {
+            ('0'..='9').chain('a'..='z').collect()
+        };
+
+        return names
+            .into_iter()
+            .filter_map(
+                |name| {
+                    let entry = registers.get(name);
+
+                    if entry.is_none() && !include_empty {
+                        return None;
+                    }
+
+                    let label = entry.map_or_else(
+                        || {
+                            return String::from("<empty>");
+                        },
+                        |entry| {
+                            return entry.text.replace('\n', "⏎");
+                        },
+                    );
+
+                    return Some(
+                        PickerItem {
+                            key: name,
+                            label,
+                        }
+                    );
+                }
+            )
+            .collect();
+    }
+
     fn save_file(&mut self, file_name: Option<&str>) {
+        let file_path = file_name
+            .map(PathBuf::from)
+            .or_else(
+                || {
+                    return self.view.get_file_path().map(Path::to_path_buf);
+                }
+            );
+        let previous_contents = file_path.as_deref().map_or(
+            Ok(None),
+            |file_path| {
+                return self.local_history.read_current(file_path);
+            }
+        );
         let result = if let Some(name) = file_name {
             self.view.save_as(name)
         } else {
             self.view.save()
         };
 
-        if result.is_ok() {
+        if let Err(error) = result {
+            if error.kind() == ErrorKind::InvalidData {
+                self.update_hint(&format!("[ Some characters cannot be saved as {} ]", self.view.get_current_status().encoding));
+            } else if error.kind() == ErrorKind::PermissionDenied {
+                self.update_hint("[ Error saving the file. Its directory must be writable ]");
+            } else {
+                self.update_hint("[ Error saving the file ]");
+            }
+        } else if self.keep_previous_version(file_path.as_deref(), previous_contents).is_err() {
+            self.update_hint("[ Saved the file, but could not keep its previous version ]");
+        } else if self.save_global_marks().is_err() {
+            self.update_hint("[ Saved the file, but not its global marks ]");
+        } else {
             self.update_hint("[ Successfully saved the file ]");
+        }
+    }
+
+    fn keep_previous_version(&self, file_path: Option<&Path>, previous_contents: Result<Option<Vec<u8>>, Error>) -> Result<(), Error> {
+        let (Some(file_path), Some(contents)) = (file_path, previous_contents?) else {
+            return Ok(());
+        };
+
+        return self.local_history.save_version(file_path, &contents);
+    }
+
+    fn load_file(&mut self, file: &str) -> bool {
+        if self.view.load(file).is_err() {
+            return false;
+        }
+
+        for (name, location) in GlobalMarks::get_for_file(Path::new(file)).unwrap_or_default() {
+            self.view.set_mark_at(name, location);
+        }
+
+        if self.view.has_normalized_line_endings() {
+            self.update_hint("[ Mixed line endings were converted to CRLF ]");
+        }
+
+        return true;
+    }
+
+    fn save_global_marks(&self) -> Result<(), Error> {
+        if let Some(file_path) = self.view.get_file_path() {
+            return GlobalMarks::save_for_file(file_path, &self.view.get_global_marks());
+        }
+
+        return Ok(());
+    }
+
+    fn handle_set_mark(&mut self, name: char) {
+        self.view.set_mark(name);
+
+        if name.is_ascii_uppercase() && self.save_global_marks().is_err() {
+            self.update_hint("[ Error saving the global mark ]");
         } else {
-            self.update_hint("[ Error saving the file ]");
+            self.update_hint(&format!("[ Set mark {name} ]"));
+        }
+    }
+
+    fn handle_jump_to_mark(&mut self, name: char) {
+        if self.view.jump_to_mark(name) {
+            return;
+        }
+
+        let Some((path, location)) = GlobalMarks::get_all().unwrap_or_default().remove(&name) else {
+            self.update_hint(&format!("[ Mark {name} is not set ]"));
+
+            return;
+        };
+        let is_current_file = self.view.get_file_path().is_some_and(
+            |file_path| {
+                return GlobalMarks::get_key(file_path) == path;
+            }
+        );
+
+        if !is_current_file {
+            if self.view.get_current_status().modified {
+                self.update_hint("[ Save the file before jumping to another file ]");
+
+                return;
+            }
+
+            if !path.to_str().is_some_and(|file| { return self.load_file(file); }) {
+                self.update_hint("[ Error opening the file ]");
+
+                return;
+            }
         }
+
+        self.view.set_mark_at(name, location);
+        self.view.jump_to_mark(name);
+    }
+
+    fn get_mark_items(&self, include_empty: bool) -> Vec<PickerItem> {
+        let global_marks = GlobalMarks::get_all().unwrap_or_default();
+
+        return ('a'..='z')
+            .chain('A'..='Z')
+            .filter_map(
+                |name| {
+                    let label = self.view
+                        .get_mark_label(name)
+                        .or_else(
+                            || {
+                                if !name.is_ascii_uppercase() {
+                                    return None;
+                                }
+
+                                let (path, location) = global_marks.get(&name)?;
+
+                                return Some(
+                                    format!(
+                                        "{}:{}:{}",
+                                        path.display(),
+                                        location.line_index.saturating_add(1),
+                                        location.grapheme_index.saturating_add(1),
+                                    )
+                                );
+                            }
+                        );
+
+                    if label.is_none() && !include_empty {
+                        return None;
+                    }
+
+                    return Some(
+                        PickerItem {
+                            key: name,
+                            label: label.unwrap_or_else(|| { return String::from("<empty>"); }),
+                        }
+                    );
+                }
+            )
+            .collect();
     }
 
     fn set_prompt(&mut self, prompt_type: PromptType) {
@@ -336,11 +1491,85 @@ impl Editor {
             PromptType::Save => {
                 self.commandbar.set_prompt("[ COMMAND ] :: Save as: ");
             },
+            PromptType::GotoLine => {
+                self.commandbar.set_prompt("[ COMMAND ] :: Go to line: ");
+            },
+            PromptType::CopyToRegister => {
+                self.picker.set_items("Copy to register", self.get_register_items(true));
+                self.commandbar.set_prompt("[ COMMAND ] :: Copy to register: ");
+            },
+            PromptType::PasteFromRegister => {
+                self.picker.set_items("Paste from register", self.get_register_items(false));
+                self.commandbar.set_prompt("[ COMMAND ] :: Paste from register: ");
+            },
+            PromptType::PlayMacroRepeatedly => {
+                self.commandbar.set_prompt("[ COMMAND ] :: Play macro how many times: ");
+            },
+            PromptType::SaveMacro => {
+                self.commandbar.set_prompt("[ COMMAND ] :: Save macro as: ");
+            },
+            PromptType::LoadMacro => {
+                self.picker.set_items("Load macro", Self::get_macro_items());
+                self.commandbar.set_prompt("[ COMMAND ] :: Load macro: ");
+            },
+            PromptType::Transform => {
+                self.picker.set_items("Transform", Self::get_transform_items());
+                self.commandbar.set_prompt("[ COMMAND ] :: Transform: ");
+            },
+            PromptType::LineOperation => {
+                self.picker.set_items("Lines", Self::get_line_operation_items());
+                self.commandbar.set_prompt("[ COMMAND ] :: Lines: ");
+            },
+            PromptType::SetMark => {
+                self.picker.set_items("Set mark (a-z in this file, A-Z across files)", self.get_mark_items(true));
+                self.commandbar.set_prompt("[ COMMAND ] :: Set mark: ");
+            },
+            PromptType::JumpToMark => {
+                self.picker.set_items("Jump to mark", self.get_mark_items(false));
+                self.commandbar.set_prompt("[ COMMAND ] :: Jump to mark: ");
+            },
+            PromptType::LineEnding => {
+                self.picker.set_items("Line endings", Self::get_line_ending_items());
+                self.commandbar.set_prompt("[ COMMAND ] :: Line endings: ");
+            },
+            PromptType::Encoding => {
+                self.picker.set_items("Encoding", Self::get_encoding_items());
+                self.commandbar.set_prompt("[ COMMAND ] :: Encoding: ");
+            },
+            PromptType::History => {
+                self.picker.set_items("History", self.get_history_items());
+                self.commandbar.set_prompt("[ COMMAND ] :: Version: ");
+            },
+            PromptType::HistoryVersion => {
+                let title = self.selected_version.as_ref().map(HistoryVersion::get_timestamp).unwrap_or_default();
+
+                self.picker.set_items(&title, Self::get_history_version_items());
+                self.commandbar.set_prompt("[ COMMAND ] :: Version: ");
+            },
+            PromptType::HistoryDiff => {
+                let items = self.get_history_diff_items().unwrap_or_default();
+                let added_count = items.iter().filter(|item| { return item.key == '+'; }).count();
+                let removed_count = items.iter().filter(|item| { return item.key == '-'; }).count();
+                let title = format!(
+                    "{} -> buffer (+{added_count} -{removed_count})",
+                    self.selected_version.as_ref().map(HistoryVersion::get_timestamp).unwrap_or_default(),
+                );
+
+                self.picker.set_items(&title, items);
+                self.commandbar.set_prompt("[ COMMAND ] :: Diff: ");
+            },
+            PromptType::SortByColumn => {
+                self.commandbar.set_prompt("[ COMMAND ] :: Sort by column (N, then an optional delimiter): ");
+            },
             PromptType::None => {
                 self.hintbar.set_needs_redraw(true);
             },
         }
 
+        if self.prompt_type.has_picker() || prompt_type.has_picker() {
+            self.view.set_needs_redraw(true);
+        }
+
         self.commandbar.clear_value();
         self.prompt_type = prompt_type;
     }
@@ -371,7 +1600,19 @@ impl Editor {
         }
 
         if self.terminal_size.height > 2 {
+            if self.prompt_type.has_picker() && self.view.get_needs_redraw() {
+                self.picker.set_needs_redraw(true);
+            }
+
             self.view.render(0);
+
+            if self.prompt_type.has_picker() {
+                self.picker.render(
+                    self.terminal_size.height
+                        .saturating_sub(2)
+                        .saturating_sub(self.picker.get_height())
+                );
+            }
         }
 
         let new_cursor_position = if self.is_in_prompt() {
@@ -383,6 +1624,12 @@ impl Editor {
             self.view.get_cursor_position()
         };
 
+        if self.view.is_overwrite_mode() && !self.is_in_prompt() {
+            let _ = Terminal::set_overwrite_cursor_style();
+        } else {
+            let _ = Terminal::set_default_cursor_style();
+        }
+
         Terminal::move_cursor_to(new_cursor_position);
         Terminal::show_cursor();
         Terminal::execute();
@@ -30,7 +30,14 @@ impl<'a> Iterator for AnnotatedStringIterator<'a> {
             )
             .last() {
             let start_index = self.current_index;
-            let end_index = min(annotation.end_byte_index, self.annotated_string.string.len());
+            let mut end_index = min(annotation.end_byte_index, self.annotated_string.string.len());
+
+            for other_annotation in &self.annotated_string.annotations {
+                if other_annotation.start_byte_index > self.current_index &&
+                    other_annotation.start_byte_index < end_index {
+                    end_index = other_annotation.start_byte_index;
+                }
+            }
 
             self.current_index = end_index;
 
@@ -2,4 +2,8 @@
 pub enum AnnotationType {
     Match,
     SelectedMatch,
+    Selection,
+    Cursor,
+    MatchingBracket,
+    Mark,
 }
@@ -40,6 +40,13 @@ impl AnnotatedString {
         );
     }
 
+    pub fn append(&mut self, string: &str, annotation_type: AnnotationType) {
+        let start_byte_index = self.string.len();
+
+        self.string.push_str(string);
+        self.add_annotation(annotation_type, start_byte_index, self.string.len());
+    }
+
     pub fn replace(&mut self, start_byte_index: usize, end_byte_index: usize, new_string: &str) {
         let end_byte_index = min(end_byte_index, self.string.len());
 
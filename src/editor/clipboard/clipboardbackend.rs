@@ -0,0 +1,75 @@
+use std::{
+    env,
+    path::Path,
+};
+
+#[derive(Default, Copy, Clone, Eq, PartialEq)]
+pub enum ClipboardBackend {
+    #[default]
+    Internal,
+    Osc52,
+    WlClipboard,
+    Xclip,
+}
+
+impl ClipboardBackend {
+    pub fn detect() -> Self {
+        if env::var_os("SSH_TTY").is_some() || env::var_os("SSH_CONNECTION").is_some() {
+            return Self::Osc52;
+        }
+
+        if env::var_os("WAYLAND_DISPLAY").is_some() && Self::is_installed("wl-copy") && Self::is_installed("wl-paste") {
+            return Self::WlClipboard;
+        }
+
+        if env::var_os("DISPLAY").is_some() && Self::is_installed("xclip") {
+            return Self::Xclip;
+        }
+
+        if env::var("TERM").is_ok_and(|term| { return term != "linux" && term != "dumb"; }) {
+            return Self::Osc52;
+        }
+
+        return Self::Internal;
+    }
+
+    pub const fn copy_command(self) -> Option<(&'static str, &'static [&'static str])> {
+        match self {
+            Self::WlClipboard => {
+                return Some(("wl-copy", &[]));
+            },
+            Self::Xclip => {
+                return Some(("xclip", &["-selection", "clipboard", "-in"]));
+            },
+            Self::Internal | Self::Osc52 => {
+                return None;
+            },
+        }
+    }
+
+    pub const fn paste_command(self) -> Option<(&'static str, &'static [&'static str])> {
+        match self {
+            Self::WlClipboard => {
+                return Some(("wl-paste", &["--no-newline"]));
+            },
+            Self::Xclip => {
+                return Some(("xclip", &["-selection", "clipboard", "-out"]));
+            },
+            Self::Internal | Self::Osc52 => {
+                return None;
+            },
+        }
+    }
+
+    fn is_installed(program: &str) -> bool {
+        return env::var_os("PATH").is_some_and(
+            |paths| {
+                return env::split_paths(&paths).any(
+                    |directory| {
+                        return Path::new(&directory).join(program).is_file();
+                    }
+                );
+            }
+        );
+    }
+}
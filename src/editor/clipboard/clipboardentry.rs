@@ -0,0 +1,16 @@
+#[derive(Default, Clone, Eq, PartialEq)]
+pub struct ClipboardEntry {
+    pub text: String,
+    pub linewise: bool,
+    pub blockwise: bool,
+}
+
+impl ClipboardEntry {
+    pub fn from(text: &str) -> Self {
+        return Self {
+            text: text.replace("\r\n", "\n").replace('\r', "\n"),
+            linewise: false,
+            blockwise: false,
+        };
+    }
+}
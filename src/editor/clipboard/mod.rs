@@ -0,0 +1,90 @@
+mod clipboardbackend;
+mod clipboardentry;
+
+use std::{
+    io::{
+        Error,
+        Write,
+    },
+    process::{
+        Command,
+        Stdio,
+    },
+};
+use clipboardbackend::ClipboardBackend;
+pub use clipboardentry::ClipboardEntry;
+use super::Terminal;
+
+pub struct Clipboard {
+    entry: ClipboardEntry,
+    backend: ClipboardBackend,
+}
+
+impl Default for Clipboard {
+    fn default() -> Self {
+        return Self {
+            entry: ClipboardEntry::default(),
+            backend: ClipboardBackend::detect(),
+        };
+    }
+}
+
+impl Clipboard {
+    pub fn copy(&mut self, entry: ClipboardEntry) -> Result<(), Error> {
+        self.entry = entry;
+
+        if self.backend == ClipboardBackend::Osc52 {
+            Terminal::copy_to_clipboard(&self.entry.text)?;
+            Terminal::execute()?;
+        } else if let Some((program, args)) = self.backend.copy_command() {
+            let mut child = Command::new(program)
+                .args(args)
+                .stdin(Stdio::piped())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .spawn()?;
+
+            if let Some(mut stdin) = child.stdin.take() {
+                stdin.write_all(self.entry.text.as_bytes())?;
+            }
+
+            if !child.wait()?.success() {
+                return Err(
+                    Error::other(
+                        format!("{program} failed")
+                    )
+                );
+            }
+        }
+
+        return Ok(());
+    }
+
+    pub fn paste(&self) -> ClipboardEntry {
+        if let Some(text) = self.read_external() {
+            let entry = ClipboardEntry::from(&text);
+
+            if entry.text != self.entry.text {
+                return entry;
+            }
+        }
+
+        return self.entry.clone();
+    }
+
+    fn read_external(&self) -> Option<String> {
+        let (program, args) = self.backend.paste_command()?;
+        let output = Command::new(program)
+            .args(args)
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .output()
+            .ok()?;
+
+        if !output.status.success() {
+            return None;
+        }
+
+        return String::from_utf8(output.stdout).ok();
+    }
+}
@@ -1,8 +1,15 @@
-use std::convert::TryFrom;
+use std::{
+    convert::TryFrom,
+    fmt,
+    fmt::Display,
+};
 use crossterm::event::{
     KeyCode::{
         Char,
+        Up,
+        Down,
         Tab,
+        BackTab,
         Enter,
         Backspace,
         Delete,
@@ -10,14 +17,224 @@ use crossterm::event::{
     KeyEvent,
     KeyModifiers,
 };
+use super::{
+    Transform,
+    LineOperation,
+};
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Eq, PartialEq)]
 pub enum Edit {
     InsertCharacter(char),
     InsertTab,
     InsertLine,
     DeletePrevious,
     DeleteNext,
+    DeleteWordPrevious,
+    DeleteWordNext,
+    DuplicateLine,
+    DeleteLine,
+    MoveLineUp,
+    MoveLineDown,
+    JoinLines,
+    Reflow,
+    Dedent,
+    Transform(Transform),
+    LineOperation(LineOperation),
+    Increment(i64),
+    IncrementSequence(i64),
+    Undo,
+    Redo,
+}
+
+impl Edit {
+    const NAMES: [(Self, &str); 15] = [
+        (
+            Self::InsertTab,
+            "insert-tab",
+        ),
+        (
+            Self::InsertLine,
+            "insert-line",
+        ),
+        (
+            Self::DeletePrevious,
+            "delete-previous",
+        ),
+        (
+            Self::DeleteNext,
+            "delete-next",
+        ),
+        (
+            Self::DeleteWordPrevious,
+            "delete-word-previous",
+        ),
+        (
+            Self::DeleteWordNext,
+            "delete-word-next",
+        ),
+        (
+            Self::DuplicateLine,
+            "duplicate-line",
+        ),
+        (
+            Self::DeleteLine,
+            "delete-line",
+        ),
+        (
+            Self::MoveLineUp,
+            "move-line-up",
+        ),
+        (
+            Self::MoveLineDown,
+            "move-line-down",
+        ),
+        (
+            Self::JoinLines,
+            "join-lines",
+        ),
+        (
+            Self::Reflow,
+            "reflow",
+        ),
+        (
+            Self::Dedent,
+            "dedent",
+        ),
+        (
+            Self::Undo,
+            "undo",
+        ),
+        (
+            Self::Redo,
+            "redo",
+        ),
+    ];
+}
+
+impl Display for Edit {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        if let Self::InsertCharacter(character) = self {
+            return write!(
+                formatter,
+                "insert-character-{:x}",
+                u32::from(*character)
+            );
+        }
+
+        if let Self::Transform(transform) = self {
+            return write!(
+                formatter,
+                "transform-{transform}"
+            );
+        }
+
+        if let Self::Increment(delta) = self {
+            return write!(
+                formatter,
+                "increment-{delta}"
+            );
+        }
+
+        if let Self::IncrementSequence(delta) = self {
+            return write!(
+                formatter,
+                "increment-sequence-{delta}"
+            );
+        }
+
+        if let Self::LineOperation(operation) = self {
+            return write!(
+                formatter,
+                "lines-{operation}"
+            );
+        }
+
+        let name = Self::NAMES
+            .iter()
+            .find(
+                |(command, _)| {
+                    return command == self;
+                }
+            )
+            .map_or(
+                "",
+                |(_, name)| {
+                    return name;
+                }
+            );
+
+        return write!(
+            formatter,
+            "{}",
+            name
+        );
+    }
+}
+
+impl TryFrom<&str> for Edit {
+    type Error = String;
+
+    fn try_from(name: &str) -> Result<Self, Self::Error> {
+        if let Some(code) = name.strip_prefix("insert-character-") {
+            return u32::from_str_radix(code, 16)
+                .ok()
+                .and_then(char::from_u32)
+                .map(Self::InsertCharacter)
+                .ok_or_else(
+                    || {
+                        return format!("Invalid character code: {code}");
+                    }
+                );
+        }
+
+        if let Some(transform) = name.strip_prefix("transform-") {
+            return Transform::try_from(transform).map(Self::Transform);
+        }
+
+        if let Some(delta) = name.strip_prefix("increment-sequence-") {
+            return delta
+                .parse::<i64>()
+                .map(Self::IncrementSequence)
+                .map_err(
+                    |_| {
+                        return format!("Invalid increment: {delta}");
+                    }
+                );
+        }
+
+        if let Some(delta) = name.strip_prefix("increment-") {
+            return delta
+                .parse::<i64>()
+                .map(Self::Increment)
+                .map_err(
+                    |_| {
+                        return format!("Invalid increment: {delta}");
+                    }
+                );
+        }
+
+        if let Some(operation) = name.strip_prefix("lines-") {
+            return LineOperation::try_from(operation).map(Self::LineOperation);
+        }
+
+        return Self::NAMES
+            .iter()
+            .find(
+                |(_, command_name)| {
+                    return *command_name == name;
+                }
+            )
+            .map(
+                |(command, _)| {
+                    return *command;
+                }
+            )
+            .ok_or_else(
+                || {
+                    return format!("Unknown edit command: {name}");
+                }
+            );
+    }
 }
 
 impl TryFrom<KeyEvent> for Edit {
@@ -33,6 +250,86 @@ impl TryFrom<KeyEvent> for Edit {
                     Self::InsertCharacter(character)
                 );
             },
+            (
+                Char('z'),
+                KeyModifiers::CONTROL
+            ) => {
+                return Ok(
+                    Self::Undo
+                );
+            },
+            (
+                Char('y'),
+                KeyModifiers::CONTROL
+            ) => {
+                return Ok(
+                    Self::Redo
+                );
+            },
+            (
+                Char('D' | 'd'),
+                modifiers
+            ) if modifiers == KeyModifiers::CONTROL | KeyModifiers::SHIFT => {
+                return Ok(
+                    Self::DuplicateLine
+                );
+            },
+            (
+                Char('K' | 'k'),
+                modifiers
+            ) if modifiers == KeyModifiers::CONTROL | KeyModifiers::SHIFT => {
+                return Ok(
+                    Self::DeleteLine
+                );
+            },
+            (
+                Up,
+                KeyModifiers::ALT
+            ) => {
+                return Ok(
+                    Self::MoveLineUp
+                );
+            },
+            (
+                Char('q'),
+                KeyModifiers::ALT
+            ) => {
+                return Ok(
+                    Self::Reflow
+                );
+            },
+            (
+                Char('u'),
+                KeyModifiers::ALT
+            ) => {
+                return Ok(
+                    Self::Transform(Transform::UpperCase)
+                );
+            },
+            (
+                Char('l'),
+                KeyModifiers::ALT
+            ) => {
+                return Ok(
+                    Self::Transform(Transform::LowerCase)
+                );
+            },
+            (
+                Down,
+                KeyModifiers::ALT
+            ) => {
+                return Ok(
+                    Self::MoveLineDown
+                );
+            },
+            (
+                Char('j'),
+                KeyModifiers::CONTROL
+            ) => {
+                return Ok(
+                    Self::JoinLines
+                );
+            },
             (
                 Tab,
                 KeyModifiers::NONE
@@ -41,6 +338,14 @@ impl TryFrom<KeyEvent> for Edit {
                     Self::InsertTab
                 );
             },
+            (
+                BackTab,
+                _
+            ) => {
+                return Ok(
+                    Self::Dedent
+                );
+            },
             (
                 Enter,
                 KeyModifiers::NONE
@@ -65,6 +370,22 @@ impl TryFrom<KeyEvent> for Edit {
                     Self::DeleteNext
                 );
             },
+            (
+                Backspace | Char('h'),
+                KeyModifiers::CONTROL
+            ) => {
+                return Ok(
+                    Self::DeleteWordPrevious
+                );
+            },
+            (
+                Delete,
+                KeyModifiers::CONTROL
+            ) => {
+                return Ok(
+                    Self::DeleteWordNext
+                );
+            },
             _ => {
                 return Err(String::new());
             },
@@ -0,0 +1,121 @@
+use std::convert::TryFrom;
+use crossterm::event::{
+    KeyCode,
+    KeyEvent,
+    KeyModifiers,
+};
+
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub struct KeyBinding {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    pub fn matches(&self, event: &KeyEvent) -> bool {
+        if event.modifiers != self.modifiers {
+            return false;
+        }
+
+        match (event.code, self.code) {
+            (KeyCode::Char(event_character), KeyCode::Char(character)) => {
+                return event_character.eq_ignore_ascii_case(&character);
+            },
+            (event_code, code) => {
+                return event_code == code;
+            },
+        }
+    }
+}
+
+impl TryFrom<&str> for KeyBinding {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut parts = value.trim().split('+').map(str::trim).peekable();
+        let mut key = "";
+
+        while let Some(part) = parts.next() {
+            if parts.peek().is_none() {
+                key = part;
+
+                break;
+            }
+
+            match part.to_lowercase().as_str() {
+                "ctrl" | "control" => {
+                    modifiers |= KeyModifiers::CONTROL;
+                },
+                "alt" | "meta" => {
+                    modifiers |= KeyModifiers::ALT;
+                },
+                "shift" => {
+                    modifiers |= KeyModifiers::SHIFT;
+                },
+                _ => {
+                    return Err(format!("Unknown modifier: {part}"));
+                },
+            }
+        }
+
+        let mut characters = key.chars();
+        let code = match (characters.next(), characters.next()) {
+            (Some(character), None) => {
+                KeyCode::Char(character.to_ascii_lowercase())
+            },
+            _ => {
+                match key.to_lowercase().as_str() {
+                    "up" => {
+                        KeyCode::Up
+                    },
+                    "down" => {
+                        KeyCode::Down
+                    },
+                    "left" => {
+                        KeyCode::Left
+                    },
+                    "right" => {
+                        KeyCode::Right
+                    },
+                    "home" => {
+                        KeyCode::Home
+                    },
+                    "end" => {
+                        KeyCode::End
+                    },
+                    "pageup" => {
+                        KeyCode::PageUp
+                    },
+                    "pagedown" => {
+                        KeyCode::PageDown
+                    },
+                    "insert" => {
+                        KeyCode::Insert
+                    },
+                    "delete" => {
+                        KeyCode::Delete
+                    },
+                    "space" => {
+                        KeyCode::Char(' ')
+                    },
+                    name => {
+                        name
+                            .strip_prefix('f')
+                            .and_then(|number| { return number.parse::<u8>().ok(); })
+                            .filter(|number| { return (1..=12).contains(number); })
+                            .map(KeyCode::F)
+                            .ok_or_else(|| { return format!("Unknown key: {key}"); })?
+                    },
+                }
+            },
+        };
+
+        return Ok(
+            Self {
+                code,
+                modifiers,
+            }
+        );
+    }
+}
@@ -0,0 +1,201 @@
+use std::{
+    convert::TryFrom,
+    fmt,
+    fmt::Display,
+};
+
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub enum LineOperation {
+    SortLexical,
+    SortCaseInsensitive,
+    SortNatural,
+    SortNumeric,
+    SortByColumn(usize, Option<char>),
+    Reverse,
+    Unique,
+    Shuffle,
+}
+
+impl LineOperation {
+    const NAMES: [(Self, &str); 7] = [
+        (
+            Self::SortLexical,
+            "sort-lexical",
+        ),
+        (
+            Self::SortCaseInsensitive,
+            "sort-case-insensitive",
+        ),
+        (
+            Self::SortNatural,
+            "sort-natural",
+        ),
+        (
+            Self::SortNumeric,
+            "sort-numeric",
+        ),
+        (
+            Self::Reverse,
+            "reverse",
+        ),
+        (
+            Self::Unique,
+            "unique",
+        ),
+        (
+            Self::Shuffle,
+            "shuffle",
+        ),
+    ];
+
+    pub const PICKER_ITEMS: [(char, &str); 8] = [
+        ('s', "Sort"),
+        ('i', "Sort ignoring case"),
+        ('n', "Sort naturally (file2 before file10)"),
+        ('m', "Sort numerically"),
+        ('c', "Sort by column..."),
+        ('r', "Reverse"),
+        ('u', "Remove duplicates"),
+        ('x', "Shuffle"),
+    ];
+
+    pub const fn from_key(key: char) -> Option<Self> {
+        match key {
+            's' => {
+                return Some(Self::SortLexical);
+            },
+            'i' => {
+                return Some(Self::SortCaseInsensitive);
+            },
+            'n' => {
+                return Some(Self::SortNatural);
+            },
+            'm' => {
+                return Some(Self::SortNumeric);
+            },
+            'r' => {
+                return Some(Self::Reverse);
+            },
+            'u' => {
+                return Some(Self::Unique);
+            },
+            'x' => {
+                return Some(Self::Shuffle);
+            },
+            _ => {
+                return None;
+            },
+        }
+    }
+
+    pub fn parse_column(value: &str) -> Option<Self> {
+        let value = value.trim();
+        let digits_length = value
+            .find(
+                |character: char| {
+                    return !character.is_ascii_digit();
+                }
+            )
+            .unwrap_or(value.len());
+        let column = value[..digits_length].parse::<usize>().ok().filter(|column| { return *column > 0; })?;
+        let mut delimiter = value[digits_length..].trim().chars();
+
+        match (delimiter.next(), delimiter.next()) {
+            (None, _) => {
+                return Some(Self::SortByColumn(column, None));
+            },
+            (Some(delimiter), None) => {
+                return Some(Self::SortByColumn(column, Some(delimiter)));
+            },
+            (Some(_), Some(_)) => {
+                return None;
+            },
+        }
+    }
+}
+
+impl Display for LineOperation {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        if let Self::SortByColumn(column, Some(delimiter)) = self {
+            return write!(
+                formatter,
+                "sort-by-column-{column}-{:x}",
+                u32::from(*delimiter)
+            );
+        }
+
+        if let Self::SortByColumn(column, None) = self {
+            return write!(
+                formatter,
+                "sort-by-column-{column}"
+            );
+        }
+
+        let name = Self::NAMES
+            .iter()
+            .find(
+                |(command, _)| {
+                    return command == self;
+                }
+            )
+            .map_or(
+                "",
+                |(_, name)| {
+                    return name;
+                }
+            );
+
+        return write!(
+            formatter,
+            "{}",
+            name
+        );
+    }
+}
+
+impl TryFrom<&str> for LineOperation {
+    type Error = String;
+
+    fn try_from(name: &str) -> Result<Self, Self::Error> {
+        if let Some(arguments) = name.strip_prefix("sort-by-column-") {
+            let (column, delimiter) = arguments.split_once('-').map_or((arguments, None), |(column, delimiter)| { return (column, Some(delimiter)); });
+            let column = column.parse::<usize>().map_err(|_| { return format!("Invalid column: {column}"); })?;
+            let delimiter = delimiter
+                .map(
+                    |code| {
+                        return u32::from_str_radix(code, 16)
+                            .ok()
+                            .and_then(char::from_u32)
+                            .ok_or_else(
+                                || {
+                                    return format!("Invalid delimiter code: {code}");
+                                }
+                            );
+                    }
+                )
+                .transpose()?;
+
+            return Ok(
+                Self::SortByColumn(column, delimiter)
+            );
+        }
+
+        return Self::NAMES
+            .iter()
+            .find(
+                |(_, command_name)| {
+                    return *command_name == name;
+                }
+            )
+            .map(
+                |(command, _)| {
+                    return *command;
+                }
+            )
+            .ok_or_else(
+                || {
+                    return format!("Unknown line operation: {name}");
+                }
+            );
+    }
+}
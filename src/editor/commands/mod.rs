@@ -1,21 +1,105 @@
 mod editcmd;
 mod movecmd;
 mod systemcmd;
+mod transformcmd;
+mod lineoperationcmd;
+mod keybinding;
 
-use std::convert::TryFrom;
-use crossterm::event::Event;
+use std::{
+    convert::TryFrom,
+    fmt,
+    fmt::Display,
+};
+use crossterm::event::{
+    Event,
+    KeyEvent,
+    KeyModifiers,
+};
 pub use editcmd::Edit;
 pub use movecmd::Move;
 pub use systemcmd::System;
+pub use transformcmd::Transform;
+pub use lineoperationcmd::LineOperation;
+pub use keybinding::KeyBinding;
 use super::Size;
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Eq, PartialEq)]
 pub enum Command {
     Move(Move),
+    Select(Move),
+    SelectBlock(Move),
     Edit(Edit),
     System(System),
 }
 
+impl Display for Command {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Move(command) => {
+                return write!(
+                    formatter,
+                    "move:{command}"
+                );
+            },
+            Self::Select(command) => {
+                return write!(
+                    formatter,
+                    "select:{command}"
+                );
+            },
+            Self::SelectBlock(command) => {
+                return write!(
+                    formatter,
+                    "select-block:{command}"
+                );
+            },
+            Self::Edit(command) => {
+                return write!(
+                    formatter,
+                    "edit:{command}"
+                );
+            },
+            Self::System(command) => {
+                return write!(
+                    formatter,
+                    "system:{command}"
+                );
+            },
+        }
+    }
+}
+
+impl TryFrom<&str> for Command {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let Some((kind, name)) = value.split_once(':') else {
+            return Err(format!("Invalid command: {value}"));
+        };
+
+        match kind {
+            "move" => {
+                return Move::try_from(name).map(Self::Move);
+            },
+            "select" => {
+                return Move::try_from(name).map(Self::Select);
+            },
+            "select-block" => {
+                return Move::try_from(name).map(Self::SelectBlock);
+            },
+            "edit" => {
+                return Edit::try_from(name).map(Self::Edit);
+            },
+            "system" => {
+                return System::try_from(name).map(Self::System);
+            },
+            _ => {
+                return Err(format!("Invalid command: {value}"));
+            },
+        }
+    }
+}
+
 impl TryFrom<Event> for Command {
     type Error = String;
 
@@ -30,6 +114,9 @@ impl TryFrom<Event> for Command {
                         |_| Move::try_from(key_event)
                             .map(Command::Move)
                     )
+                    .or_else(
+                        |_| Self::select_from(key_event)
+                    )
                     .or_else(
                         |_| System::try_from(key_event)
                         .map(Command::System)
@@ -53,3 +140,29 @@ impl TryFrom<Event> for Command {
         }
     }
 }
+
+impl Command {
+    fn select_from(event: KeyEvent) -> Result<Self, String> {
+        if !event.modifiers.contains(KeyModifiers::SHIFT) {
+            return Err(String::new());
+        }
+
+        if event.modifiers == KeyModifiers::ALT | KeyModifiers::SHIFT {
+            return Move::try_from(
+                KeyEvent {
+                    modifiers: KeyModifiers::NONE,
+                    ..event
+                }
+            )
+                .map(Command::SelectBlock);
+        }
+
+        return Move::try_from(
+            KeyEvent {
+                modifiers: event.modifiers.difference(KeyModifiers::SHIFT),
+                ..event
+            }
+        )
+            .map(Command::Select);
+    }
+}
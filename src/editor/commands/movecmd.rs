@@ -1,6 +1,11 @@
-use std::convert::TryFrom;
+use std::{
+    convert::TryFrom,
+    fmt,
+    fmt::Display,
+};
 use crossterm::event::{
     KeyCode::{
+        Char,
         Left,
         Right,
         Up,
@@ -14,7 +19,7 @@ use crossterm::event::{
     KeyModifiers,
 };
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Eq, PartialEq)]
 pub enum Move {
     PageUp,
     PageDown,
@@ -24,6 +29,126 @@ pub enum Move {
     Left,
     Right,
     Down,
+    WordLeft,
+    WordRight,
+    ParagraphUp,
+    ParagraphDown,
+    StartOfFile,
+    EndOfFile,
+    MatchingBracket,
+}
+
+impl Move {
+    const NAMES: [(Self, &str); 15] = [
+        (
+            Self::PageUp,
+            "page-up",
+        ),
+        (
+            Self::PageDown,
+            "page-down",
+        ),
+        (
+            Self::StartOfLine,
+            "start-of-line",
+        ),
+        (
+            Self::EndOfLine,
+            "end-of-line",
+        ),
+        (
+            Self::Up,
+            "up",
+        ),
+        (
+            Self::Left,
+            "left",
+        ),
+        (
+            Self::Right,
+            "right",
+        ),
+        (
+            Self::Down,
+            "down",
+        ),
+        (
+            Self::WordLeft,
+            "word-left",
+        ),
+        (
+            Self::WordRight,
+            "word-right",
+        ),
+        (
+            Self::ParagraphUp,
+            "paragraph-up",
+        ),
+        (
+            Self::ParagraphDown,
+            "paragraph-down",
+        ),
+        (
+            Self::StartOfFile,
+            "start-of-file",
+        ),
+        (
+            Self::EndOfFile,
+            "end-of-file",
+        ),
+        (
+            Self::MatchingBracket,
+            "matching-bracket",
+        ),
+    ];
+}
+
+impl Display for Move {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let name = Self::NAMES
+            .iter()
+            .find(
+                |(command, _)| {
+                    return command == self;
+                }
+            )
+            .map_or(
+                "",
+                |(_, name)| {
+                    return name;
+                }
+            );
+
+        return write!(
+            formatter,
+            "{}",
+            name
+        );
+    }
+}
+
+impl TryFrom<&str> for Move {
+    type Error = String;
+
+    fn try_from(name: &str) -> Result<Self, Self::Error> {
+        return Self::NAMES
+            .iter()
+            .find(
+                |(_, command_name)| {
+                    return *command_name == name;
+                }
+            )
+            .map(
+                |(command, _)| {
+                    return *command;
+                }
+            )
+            .ok_or_else(
+                || {
+                    return format!("Unknown move command: {name}");
+                }
+            );
+    }
 }
 
 impl TryFrom<KeyEvent> for Move {
@@ -66,6 +191,33 @@ impl TryFrom<KeyEvent> for Move {
                     return Err(String::new());
                 },
             }
+        } else if modifiers == KeyModifiers::CONTROL {
+            match code {
+                Left => {
+                    return Ok(Self::WordLeft);
+                },
+                Right => {
+                    return Ok(Self::WordRight);
+                },
+                Up => {
+                    return Ok(Self::ParagraphUp);
+                },
+                Down => {
+                    return Ok(Self::ParagraphDown);
+                },
+                Home => {
+                    return Ok(Self::StartOfFile);
+                },
+                End => {
+                    return Ok(Self::EndOfFile);
+                },
+                Char('b') => {
+                    return Ok(Self::MatchingBracket);
+                },
+                _ => {
+                    return Err(String::new());
+                },
+            }
         } else {
             return Err(String::new());
         }
@@ -1,19 +1,276 @@
-use std::convert::TryFrom;
+use std::{
+    convert::TryFrom,
+    fmt,
+    fmt::Display,
+};
 use crossterm::event::{
-    KeyCode::Char,
+    KeyCode::{
+        Char,
+        Backspace,
+        Enter,
+        Up,
+        Down,
+        Left,
+        Right,
+    },
     KeyCode,
     KeyEvent,
     KeyModifiers,
 };
 use super::Size;
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Eq, PartialEq)]
 pub enum System {
     Save,
     Resize(Size),
     Quit,
     Dismiss,
     Search,
+    Copy,
+    Cut,
+    Paste,
+    KillLine,
+    KillWordForward,
+    KillWordBackward,
+    YankPop,
+    CopyToRegister,
+    PasteFromRegister,
+    ToggleSubwordMode,
+    GotoLine,
+    AddCursorAbove,
+    AddCursorBelow,
+    AddCursorAtNextMatch,
+    AddCursorsAtAllMatches,
+    ToggleMacroRecording,
+    PlayMacro,
+    PlayMacroRepeatedly,
+    SaveMacro,
+    LoadMacro,
+    ToggleAutoIndent,
+    ToggleOverwriteMode,
+    TransformSelection,
+    SortLines,
+    JumpBack,
+    JumpForward,
+    PreviousChange,
+    NextChange,
+    SetMark,
+    JumpToMark,
+    SetLineEnding,
+    SetEncoding,
+    ShowHistory,
+}
+
+impl System {
+    const NAMES: [(Self, &str); 37] = [
+        (
+            Self::Save,
+            "save",
+        ),
+        (
+            Self::Quit,
+            "quit",
+        ),
+        (
+            Self::Dismiss,
+            "dismiss",
+        ),
+        (
+            Self::Search,
+            "search",
+        ),
+        (
+            Self::Copy,
+            "copy",
+        ),
+        (
+            Self::Cut,
+            "cut",
+        ),
+        (
+            Self::Paste,
+            "paste",
+        ),
+        (
+            Self::KillLine,
+            "kill-line",
+        ),
+        (
+            Self::KillWordForward,
+            "kill-word-forward",
+        ),
+        (
+            Self::KillWordBackward,
+            "kill-word-backward",
+        ),
+        (
+            Self::YankPop,
+            "yank-pop",
+        ),
+        (
+            Self::CopyToRegister,
+            "copy-to-register",
+        ),
+        (
+            Self::PasteFromRegister,
+            "paste-from-register",
+        ),
+        (
+            Self::ToggleSubwordMode,
+            "toggle-subword-mode",
+        ),
+        (
+            Self::GotoLine,
+            "goto-line",
+        ),
+        (
+            Self::AddCursorAbove,
+            "add-cursor-above",
+        ),
+        (
+            Self::AddCursorBelow,
+            "add-cursor-below",
+        ),
+        (
+            Self::AddCursorAtNextMatch,
+            "add-cursor-at-next-match",
+        ),
+        (
+            Self::AddCursorsAtAllMatches,
+            "add-cursors-at-all-matches",
+        ),
+        (
+            Self::ToggleMacroRecording,
+            "toggle-macro-recording",
+        ),
+        (
+            Self::PlayMacro,
+            "play-macro",
+        ),
+        (
+            Self::PlayMacroRepeatedly,
+            "play-macro-repeatedly",
+        ),
+        (
+            Self::SaveMacro,
+            "save-macro",
+        ),
+        (
+            Self::LoadMacro,
+            "load-macro",
+        ),
+        (
+            Self::ToggleAutoIndent,
+            "toggle-auto-indent",
+        ),
+        (
+            Self::ToggleOverwriteMode,
+            "toggle-overwrite-mode",
+        ),
+        (
+            Self::TransformSelection,
+            "transform-selection",
+        ),
+        (
+            Self::SortLines,
+            "sort-lines",
+        ),
+        (
+            Self::JumpBack,
+            "jump-back",
+        ),
+        (
+            Self::JumpForward,
+            "jump-forward",
+        ),
+        (
+            Self::PreviousChange,
+            "previous-change",
+        ),
+        (
+            Self::NextChange,
+            "next-change",
+        ),
+        (
+            Self::SetMark,
+            "set-mark",
+        ),
+        (
+            Self::JumpToMark,
+            "jump-to-mark",
+        ),
+        (
+            Self::SetLineEnding,
+            "set-line-ending",
+        ),
+        (
+            Self::SetEncoding,
+            "set-encoding",
+        ),
+        (
+            Self::ShowHistory,
+            "show-history",
+        ),
+    ];
+
+    pub const fn is_macro_command(&self) -> bool {
+        return matches!(
+            self,
+            Self::ToggleMacroRecording
+            | Self::PlayMacro
+            | Self::PlayMacroRepeatedly
+            | Self::SaveMacro
+            | Self::LoadMacro
+        );
+    }
+}
+
+impl Display for System {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let name = Self::NAMES
+            .iter()
+            .find(
+                |(command, _)| {
+                    return command == self;
+                }
+            )
+            .map_or(
+                "",
+                |(_, name)| {
+                    return name;
+                }
+            );
+
+        return write!(
+            formatter,
+            "{}",
+            name
+        );
+    }
+}
+
+impl TryFrom<&str> for System {
+    type Error = String;
+
+    fn try_from(name: &str) -> Result<Self, Self::Error> {
+        return Self::NAMES
+            .iter()
+            .find(
+                |(_, command_name)| {
+                    return *command_name == name;
+                }
+            )
+            .map(
+                |(command, _)| {
+                    return *command;
+                }
+            )
+            .ok_or_else(
+                || {
+                    return format!("Unknown system command: {name}");
+                }
+            );
+    }
 }
 
 impl TryFrom<KeyEvent> for System {
@@ -43,6 +300,204 @@ impl TryFrom<KeyEvent> for System {
                         Self::Search
                     );
                 },
+                Char('c') => {
+                    return Ok(
+                        Self::Copy
+                    );
+                },
+                Char('x') => {
+                    return Ok(
+                        Self::Cut
+                    );
+                },
+                Char('v') => {
+                    return Ok(
+                        Self::Paste
+                    );
+                },
+                Char('k') => {
+                    return Ok(
+                        Self::KillLine
+                    );
+                },
+                Char('g') => {
+                    return Ok(
+                        Self::GotoLine
+                    );
+                },
+                Char('d') => {
+                    return Ok(
+                        Self::AddCursorAtNextMatch
+                    );
+                },
+                Char('r') => {
+                    return Ok(
+                        Self::ToggleMacroRecording
+                    );
+                },
+                Char('e') => {
+                    return Ok(
+                        Self::PlayMacro
+                    );
+                },
+                Char('t') => {
+                    return Ok(
+                        Self::TransformSelection
+                    );
+                },
+                _ => {
+                    return Err(String::new());
+                },
+            }
+        } else if modifiers == KeyModifiers::ALT {
+            match code {
+                Char('d') => {
+                    return Ok(
+                        Self::KillWordForward
+                    );
+                },
+                Backspace => {
+                    return Ok(
+                        Self::KillWordBackward
+                    );
+                },
+                Char('y') => {
+                    return Ok(
+                        Self::YankPop
+                    );
+                },
+                Char('c') => {
+                    return Ok(
+                        Self::CopyToRegister
+                    );
+                },
+                Char('v') => {
+                    return Ok(
+                        Self::PasteFromRegister
+                    );
+                },
+                Char('w') => {
+                    return Ok(
+                        Self::ToggleSubwordMode
+                    );
+                },
+                Enter => {
+                    return Ok(
+                        Self::AddCursorsAtAllMatches
+                    );
+                },
+                Char('e') => {
+                    return Ok(
+                        Self::PlayMacroRepeatedly
+                    );
+                },
+                Char('s') => {
+                    return Ok(
+                        Self::SaveMacro
+                    );
+                },
+                Char('m') => {
+                    return Ok(
+                        Self::LoadMacro
+                    );
+                },
+                Char('i') => {
+                    return Ok(
+                        Self::ToggleAutoIndent
+                    );
+                },
+                Char('o') => {
+                    return Ok(
+                        Self::SortLines
+                    );
+                },
+                Left => {
+                    return Ok(
+                        Self::JumpBack
+                    );
+                },
+                Right => {
+                    return Ok(
+                        Self::JumpForward
+                    );
+                },
+                Char(',') => {
+                    return Ok(
+                        Self::PreviousChange
+                    );
+                },
+                Char('.') => {
+                    return Ok(
+                        Self::NextChange
+                    );
+                },
+                Char('k') => {
+                    return Ok(
+                        Self::SetMark
+                    );
+                },
+                Char('j') => {
+                    return Ok(
+                        Self::JumpToMark
+                    );
+                },
+                Char('n') => {
+                    return Ok(
+                        Self::SetLineEnding
+                    );
+                },
+                Char('f') => {
+                    return Ok(
+                        Self::SetEncoding
+                    );
+                },
+                Char('h') => {
+                    return Ok(
+                        Self::ShowHistory
+                    );
+                },
+                _ => {
+                    return Err(String::new());
+                },
+            }
+        } else if modifiers == KeyModifiers::CONTROL | KeyModifiers::SHIFT {
+            match code {
+                Char('C' | 'c') => {
+                    return Ok(
+                        Self::Copy
+                    );
+                },
+                Char('X' | 'x') => {
+                    return Ok(
+                        Self::Cut
+                    );
+                },
+                Char('V' | 'v') => {
+                    return Ok(
+                        Self::Paste
+                    );
+                },
+                Char('L' | 'l') => {
+                    return Ok(
+                        Self::AddCursorsAtAllMatches
+                    );
+                },
+                _ => {
+                    return Err(String::new());
+                },
+            }
+        } else if modifiers == KeyModifiers::CONTROL | KeyModifiers::ALT {
+            match code {
+                Up => {
+                    return Ok(
+                        Self::AddCursorAbove
+                    );
+                },
+                Down => {
+                    return Ok(
+                        Self::AddCursorBelow
+                    );
+                },
                 _ => {
                     return Err(String::new());
                 },
@@ -51,6 +506,10 @@ impl TryFrom<KeyEvent> for System {
             return Ok(
                 Self::Dismiss
             );
+        } else if matches!(code, KeyCode::Insert) && modifiers == KeyModifiers::NONE {
+            return Ok(
+                Self::ToggleOverwriteMode
+            );
         } else {
             return Err(String::new());
         }
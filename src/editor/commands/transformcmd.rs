@@ -0,0 +1,217 @@
+use std::{
+    convert::TryFrom,
+    fmt,
+    fmt::Display,
+};
+
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub enum Transform {
+    UpperCase,
+    LowerCase,
+    TitleCase,
+    SwapCase,
+    Base64Encode,
+    Base64Decode,
+    UrlEncode,
+    UrlDecode,
+    JsonEscape,
+    JsonUnescape,
+    Sha256,
+}
+
+impl Transform {
+    pub const ALL: [Self; 11] = [
+        Self::UpperCase,
+        Self::LowerCase,
+        Self::TitleCase,
+        Self::SwapCase,
+        Self::Base64Encode,
+        Self::Base64Decode,
+        Self::UrlEncode,
+        Self::UrlDecode,
+        Self::JsonEscape,
+        Self::JsonUnescape,
+        Self::Sha256,
+    ];
+
+    const NAMES: [(Self, &str); 11] = [
+        (
+            Self::UpperCase,
+            "upper-case",
+        ),
+        (
+            Self::LowerCase,
+            "lower-case",
+        ),
+        (
+            Self::TitleCase,
+            "title-case",
+        ),
+        (
+            Self::SwapCase,
+            "swap-case",
+        ),
+        (
+            Self::Base64Encode,
+            "base64-encode",
+        ),
+        (
+            Self::Base64Decode,
+            "base64-decode",
+        ),
+        (
+            Self::UrlEncode,
+            "url-encode",
+        ),
+        (
+            Self::UrlDecode,
+            "url-decode",
+        ),
+        (
+            Self::JsonEscape,
+            "json-escape",
+        ),
+        (
+            Self::JsonUnescape,
+            "json-unescape",
+        ),
+        (
+            Self::Sha256,
+            "sha256",
+        ),
+    ];
+
+    pub const fn get_key(self) -> char {
+        match self {
+            Self::UpperCase => {
+                return 'u';
+            },
+            Self::LowerCase => {
+                return 'l';
+            },
+            Self::TitleCase => {
+                return 't';
+            },
+            Self::SwapCase => {
+                return 's';
+            },
+            Self::Base64Encode => {
+                return 'b';
+            },
+            Self::Base64Decode => {
+                return 'B';
+            },
+            Self::UrlEncode => {
+                return 'p';
+            },
+            Self::UrlDecode => {
+                return 'P';
+            },
+            Self::JsonEscape => {
+                return 'j';
+            },
+            Self::JsonUnescape => {
+                return 'J';
+            },
+            Self::Sha256 => {
+                return 'h';
+            },
+        }
+    }
+
+    pub const fn get_label(self) -> &'static str {
+        match self {
+            Self::UpperCase => {
+                return "UPPER CASE";
+            },
+            Self::LowerCase => {
+                return "lower case";
+            },
+            Self::TitleCase => {
+                return "Title Case";
+            },
+            Self::SwapCase => {
+                return "sWAP cASE";
+            },
+            Self::Base64Encode => {
+                return "Base64 encode";
+            },
+            Self::Base64Decode => {
+                return "Base64 decode";
+            },
+            Self::UrlEncode => {
+                return "URL percent-encode";
+            },
+            Self::UrlDecode => {
+                return "URL percent-decode";
+            },
+            Self::JsonEscape => {
+                return "JSON string escape";
+            },
+            Self::JsonUnescape => {
+                return "JSON string unescape";
+            },
+            Self::Sha256 => {
+                return "Insert the SHA-256 hex digest";
+            },
+        }
+    }
+
+    pub fn from_key(key: char) -> Option<Self> {
+        return Self::ALL
+            .into_iter()
+            .find(
+                |transform| {
+                    return transform.get_key() == key;
+                }
+            );
+    }
+}
+
+impl Display for Transform {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let name = Self::NAMES
+            .iter()
+            .find(
+                |(command, _)| {
+                    return command == self;
+                }
+            )
+            .map_or(
+                "",
+                |(_, name)| {
+                    return name;
+                }
+            );
+
+        return write!(
+            formatter,
+            "{}",
+            name
+        );
+    }
+}
+
+impl TryFrom<&str> for Transform {
+    type Error = String;
+
+    fn try_from(name: &str) -> Result<Self, Self::Error> {
+        return Self::NAMES
+            .iter()
+            .find(
+                |(_, command_name)| {
+                    return *command_name == name;
+                }
+            )
+            .map(
+                |(command, _)| {
+                    return *command;
+                }
+            )
+            .ok_or_else(
+                || {
+                    return format!("Unknown transform: {name}");
+                }
+            );
+    }
+}
@@ -0,0 +1,179 @@
+use std::{
+    env,
+    fs::read_to_string,
+    path::PathBuf,
+};
+use crossterm::event::{
+    KeyCode::Char,
+    KeyModifiers,
+};
+use super::{
+    line::DEFAULT_TAB_WIDTH,
+    commands::KeyBinding,
+    Encoding,
+};
+
+pub struct Config {
+    pub tab_width: usize,
+    pub indent_width: usize,
+    pub hard_tabs: bool,
+    pub auto_indent: bool,
+    pub auto_pair: bool,
+    pub detect_indentation: bool,
+    pub text_width: Option<usize>,
+    pub auto_wrap: bool,
+    pub fallback_encoding: Encoding,
+    pub history_versions: usize,
+    pub history_days: u64,
+    pub increment_key: KeyBinding,
+    pub decrement_key: KeyBinding,
+    pub increment_sequence_key: KeyBinding,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        return Self {
+            tab_width: DEFAULT_TAB_WIDTH,
+            indent_width: DEFAULT_TAB_WIDTH,
+            hard_tabs: false,
+            auto_indent: true,
+            auto_pair: true,
+            detect_indentation: true,
+            text_width: None,
+            auto_wrap: false,
+            fallback_encoding: Encoding::Windows1252,
+            history_versions: 50,
+            history_days: 30,
+            increment_key: KeyBinding {
+                code: Char('a'),
+                modifiers: KeyModifiers::CONTROL,
+            },
+            decrement_key: KeyBinding {
+                code: Char('a'),
+                modifiers: KeyModifiers::ALT,
+            },
+            increment_sequence_key: KeyBinding {
+                code: Char('a'),
+                modifiers: KeyModifiers::CONTROL | KeyModifiers::ALT,
+            },
+        };
+    }
+}
+
+impl Config {
+    pub fn load() -> Self {
+        let mut config = Self::default();
+
+        if let Some(contents) = Self::get_path().and_then(|path| { return read_to_string(path).ok(); }) {
+            config.parse(&contents);
+        }
+
+        return config;
+    }
+
+    fn get_path() -> Option<PathBuf> {
+        let config_home = env::var_os("XDG_CONFIG_HOME")
+            .filter(|config_home| { return !config_home.is_empty(); })
+            .map(PathBuf::from)
+            .or_else(
+                || {
+                    return env::var_os("HOME").map(
+                        |home| {
+                            return PathBuf::from(home).join(".config");
+                        }
+                    );
+                }
+            )?;
+
+        return Some(config_home.join("rsedit").join("config"));
+    }
+
+    fn parse(&mut self, contents: &str) {
+        for line in contents.lines() {
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let value = value.trim();
+
+            match key.trim() {
+                "tab_width" => {
+                    if let Ok(tab_width) = value.parse::<usize>() {
+                        self.tab_width = tab_width.max(1);
+                    }
+                },
+                "indent_width" => {
+                    if let Ok(indent_width) = value.parse::<usize>() {
+                        self.indent_width = indent_width.max(1);
+                    }
+                },
+                "hard_tabs" => {
+                    if let Ok(hard_tabs) = value.parse() {
+                        self.hard_tabs = hard_tabs;
+                    }
+                },
+                "auto_indent" => {
+                    if let Ok(auto_indent) = value.parse() {
+                        self.auto_indent = auto_indent;
+                    }
+                },
+                "auto_pair" => {
+                    if let Ok(auto_pair) = value.parse() {
+                        self.auto_pair = auto_pair;
+                    }
+                },
+                "detect_indentation" => {
+                    if let Ok(detect_indentation) = value.parse() {
+                        self.detect_indentation = detect_indentation;
+                    }
+                },
+                "text_width" => {
+                    if let Ok(text_width) = value.parse::<usize>() {
+                        self.text_width = Some(text_width.max(1));
+                    }
+                },
+                "auto_wrap" => {
+                    if let Ok(auto_wrap) = value.parse() {
+                        self.auto_wrap = auto_wrap;
+                    }
+                },
+                "fallback_encoding" => {
+                    if let Ok(fallback_encoding) = Encoding::try_from(value) {
+                        self.fallback_encoding = fallback_encoding;
+                    }
+                },
+                "history_versions" => {
+                    if let Ok(history_versions) = value.parse() {
+                        self.history_versions = history_versions;
+                    }
+                },
+                "history_days" => {
+                    if let Ok(history_days) = value.parse() {
+                        self.history_days = history_days;
+                    }
+                },
+                "increment_key" => {
+                    if let Ok(increment_key) = KeyBinding::try_from(value) {
+                        self.increment_key = increment_key;
+                    }
+                },
+                "decrement_key" => {
+                    if let Ok(decrement_key) = KeyBinding::try_from(value) {
+                        self.decrement_key = decrement_key;
+                    }
+                },
+                "increment_sequence_key" => {
+                    if let Ok(increment_sequence_key) = KeyBinding::try_from(value) {
+                        self.increment_sequence_key = increment_sequence_key;
+                    }
+                },
+                _ => {},
+            }
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use std::{
+    env,
+    fs::create_dir_all,
+    io::Error,
+    path::PathBuf,
+};
+
+pub struct DataDirectory;
+
+impl DataDirectory {
+    pub fn get_path() -> Option<PathBuf> {
+        if let Some(data_home) = env::var_os("XDG_DATA_HOME").filter(|data_home| { return !data_home.is_empty(); }) {
+            return Some(PathBuf::from(data_home).join("rsedit"));
+        }
+
+        return env::var_os("HOME").map(
+            |home| {
+                return PathBuf::from(home).join(".local/share/rsedit");
+            }
+        );
+    }
+
+    pub fn get_file_path(file_name: &str) -> Result<PathBuf, Error> {
+        let Some(path) = Self::get_path() else {
+            return Err(Error::other("No data directory"));
+        };
+
+        create_dir_all(&path)?;
+
+        return Ok(path.join(file_name));
+    }
+}
@@ -0,0 +1,554 @@
+use std::{
+    convert::TryFrom,
+    fmt,
+    fmt::Display,
+    io::{
+        Error,
+        ErrorKind,
+    },
+};
+use super::RawByte;
+
+const UTF8_BOM: [u8; 3] = [0xEF, 0xBB, 0xBF];
+const UTF16_LE_BOM: [u8; 2] = [0xFF, 0xFE];
+const UTF16_BE_BOM: [u8; 2] = [0xFE, 0xFF];
+const BYTES_PER_TOLERATED_INVALID_BYTE: usize = 1000;
+const MIN_UTF16_NUL_PERCENTAGE: usize = 40;
+const MAX_UTF16_NUL_PERCENTAGE_IN_OTHER_HALF: usize = 10;
+const WINDOWS_1252_HIGH_CHARS: [Option<char>; 32] = [
+    Some('€'), None, Some('‚'), Some('ƒ'), Some('„'), Some('…'), Some('†'), Some('‡'),
+    Some('ˆ'), Some('‰'), Some('Š'), Some('‹'), Some('Œ'), None, Some('Ž'), None,
+    None, Some('‘'), Some('’'), Some('“'), Some('”'), Some('•'), Some('–'), Some('—'),
+    Some('˜'), Some('™'), Some('š'), Some('›'), Some('œ'), None, Some('ž'), Some('Ÿ'),
+];
+
+#[derive(Default, Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Encoding {
+    #[default]
+    Utf8,
+    Utf8Bom,
+    Utf16Le,
+    Utf16LeBom,
+    Utf16Be,
+    Utf16BeBom,
+    Latin1,
+    Windows1252,
+}
+
+impl Encoding {
+    pub const ALL: [Self; 8] = [
+        Self::Utf8,
+        Self::Utf8Bom,
+        Self::Utf16Le,
+        Self::Utf16LeBom,
+        Self::Utf16Be,
+        Self::Utf16BeBom,
+        Self::Latin1,
+        Self::Windows1252,
+    ];
+
+    const NAMES: [(Self, &str); 8] = [
+        (
+            Self::Utf8,
+            "utf-8",
+        ),
+        (
+            Self::Utf8Bom,
+            "utf-8-bom",
+        ),
+        (
+            Self::Utf16Le,
+            "utf-16le",
+        ),
+        (
+            Self::Utf16LeBom,
+            "utf-16le-bom",
+        ),
+        (
+            Self::Utf16Be,
+            "utf-16be",
+        ),
+        (
+            Self::Utf16BeBom,
+            "utf-16be-bom",
+        ),
+        (
+            Self::Latin1,
+            "iso-8859-1",
+        ),
+        (
+            Self::Windows1252,
+            "windows-1252",
+        ),
+    ];
+
+    pub const fn get_key(self) -> char {
+        match self {
+            Self::Utf8 => {
+                return 'u';
+            },
+            Self::Utf8Bom => {
+                return 'b';
+            },
+            Self::Utf16Le => {
+                return 'l';
+            },
+            Self::Utf16LeBom => {
+                return 'm';
+            },
+            Self::Utf16Be => {
+                return 'e';
+            },
+            Self::Utf16BeBom => {
+                return 'f';
+            },
+            Self::Latin1 => {
+                return 'i';
+            },
+            Self::Windows1252 => {
+                return 'w';
+            },
+        }
+    }
+
+    pub fn from_key(key: char) -> Option<Self> {
+        return Self::ALL
+            .into_iter()
+            .find(
+                |encoding| {
+                    return encoding.get_key() == key.to_ascii_lowercase();
+                }
+            );
+    }
+
+    pub fn detect(bytes: &[u8], fallback: Self) -> Self {
+        if bytes.starts_with(&UTF8_BOM) {
+            return Self::Utf8Bom;
+        } else if bytes.starts_with(&UTF16_LE_BOM) {
+            return Self::Utf16LeBom;
+        } else if bytes.starts_with(&UTF16_BE_BOM) {
+            return Self::Utf16BeBom;
+        }
+
+        if let Some(encoding) = Self::detect_utf16_without_bom(bytes) {
+            return encoding;
+        }
+
+        let mut invalid_bytes_count: usize = 0;
+        let mut has_multibyte_chars = false;
+
+        for chunk in bytes.utf8_chunks() {
+            invalid_bytes_count = invalid_bytes_count.saturating_add(chunk.invalid().len());
+            has_multibyte_chars = has_multibyte_chars || !chunk.valid().is_ascii();
+        }
+
+        if invalid_bytes_count == 0
+            || has_multibyte_chars
+            || invalid_bytes_count.saturating_mul(BYTES_PER_TOLERATED_INVALID_BYTE) <= bytes.len() {
+            return Self::Utf8;
+        }
+
+        return fallback;
+    }
+
+    fn detect_utf16_without_bom(bytes: &[u8]) -> Option<Self> {
+        let pairs_count = bytes.len() / 2;
+
+        if pairs_count == 0 {
+            return None;
+        }
+
+        let count_nuls = |offset: usize| {
+            return bytes
+                .chunks_exact(2)
+                .filter(
+                    |pair| {
+                        return pair[offset] == 0;
+                    }
+                )
+                .count();
+        };
+        let get_percentage = |count: usize| {
+            return count.saturating_mul(100) / pairs_count;
+        };
+        let even_nuls_percentage = get_percentage(count_nuls(0));
+        let odd_nuls_percentage = get_percentage(count_nuls(1));
+
+        if odd_nuls_percentage >= MIN_UTF16_NUL_PERCENTAGE && even_nuls_percentage <= MAX_UTF16_NUL_PERCENTAGE_IN_OTHER_HALF {
+            return Some(Self::Utf16Le);
+        } else if even_nuls_percentage >= MIN_UTF16_NUL_PERCENTAGE && odd_nuls_percentage <= MAX_UTF16_NUL_PERCENTAGE_IN_OTHER_HALF {
+            return Some(Self::Utf16Be);
+        }
+
+        return None;
+    }
+
+    const fn is_little_endian(self) -> bool {
+        return matches!(self, Self::Utf16Le | Self::Utf16LeBom);
+    }
+
+    const fn get_bom(self) -> &'static [u8] {
+        match self {
+            Self::Utf8Bom => {
+                return &UTF8_BOM;
+            },
+            Self::Utf16LeBom => {
+                return &UTF16_LE_BOM;
+            },
+            Self::Utf16BeBom => {
+                return &UTF16_BE_BOM;
+            },
+            Self::Utf8 | Self::Utf16Le | Self::Utf16Be | Self::Latin1 | Self::Windows1252 => {
+                return &[];
+            },
+        }
+    }
+
+    pub fn decode(self, bytes: &[u8]) -> (String, Vec<RawByte>) {
+        let mut text = String::new();
+        let mut raw_bytes = Vec::new();
+
+        match self {
+            Self::Utf8 | Self::Utf8Bom => {
+                for chunk in bytes.strip_prefix(&UTF8_BOM).unwrap_or(bytes).utf8_chunks() {
+                    text.push_str(chunk.valid());
+
+                    for byte in chunk.invalid() {
+                        Self::push_raw_byte(&mut text, &mut raw_bytes, *byte);
+                    }
+                }
+            },
+            Self::Utf16Le | Self::Utf16LeBom | Self::Utf16Be | Self::Utf16BeBom => {
+                let is_little_endian = self.is_little_endian();
+                let bytes = bytes.strip_prefix(self.get_bom()).unwrap_or(bytes);
+                let chunks = bytes.chunks_exact(2);
+                let remainder = chunks.remainder();
+                let units = chunks.map(
+                    |chunk| {
+                        if is_little_endian {
+                            return u16::from_le_bytes([chunk[0], chunk[1]]);
+                        } else {
+                            return u16::from_be_bytes([chunk[0], chunk[1]]);
+                        }
+                    }
+                );
+
+                for result in char::decode_utf16(units) {
+                    match result {
+                        Ok(character) => {
+                            text.push(character);
+                        },
+                        Err(error) => {
+                            let unit = error.unpaired_surrogate();
+                            let unit_bytes = if is_little_endian {
+                                unit.to_le_bytes()
+                            } else {
+                                unit.to_be_bytes()
+                            };
+
+                            for byte in unit_bytes {
+                                Self::push_raw_byte(&mut text, &mut raw_bytes, byte);
+                            }
+                        },
+                    }
+                }
+
+                for byte in remainder {
+                    Self::push_raw_byte(&mut text, &mut raw_bytes, *byte);
+                }
+            },
+            Self::Latin1 => {
+                text.extend(bytes.iter().copied().map(char::from));
+            },
+            Self::Windows1252 => {
+                for byte in bytes {
+                    if (0x80..0xA0).contains(byte) {
+                        if let Some(character) = WINDOWS_1252_HIGH_CHARS[usize::from(byte - 0x80)] {
+                            text.push(character);
+                        } else {
+                            Self::push_raw_byte(&mut text, &mut raw_bytes, *byte);
+                        }
+                    } else {
+                        text.push(char::from(*byte));
+                    }
+                }
+            },
+        }
+
+        return (text, raw_bytes);
+    }
+
+    fn push_raw_byte(text: &mut String, raw_bytes: &mut Vec<RawByte>, value: u8) {
+        raw_bytes.push(
+            RawByte {
+                byte_index: text.len(),
+                value,
+            }
+        );
+        text.push(char::REPLACEMENT_CHARACTER);
+    }
+
+    pub fn encode(self, text: &str, raw_bytes: &[RawByte]) -> Result<Vec<u8>, Error> {
+        let mut bytes = self.get_bom().to_vec();
+        let mut raw_bytes = raw_bytes.iter().peekable();
+
+        for (byte_index, character) in text.char_indices() {
+            if let Some(raw_byte) = raw_bytes.next_if(|raw_byte| { return raw_byte.byte_index == byte_index; }) {
+                bytes.push(raw_byte.value);
+
+                continue;
+            }
+
+            match self {
+                Self::Utf8 | Self::Utf8Bom => {
+                    bytes.extend_from_slice(character.encode_utf8(&mut [0; 4]).as_bytes());
+                },
+                Self::Utf16Le | Self::Utf16LeBom => {
+                    for unit in character.encode_utf16(&mut [0; 2]) {
+                        bytes.extend_from_slice(&unit.to_le_bytes());
+                    }
+                },
+                Self::Utf16Be | Self::Utf16BeBom => {
+                    for unit in character.encode_utf16(&mut [0; 2]) {
+                        bytes.extend_from_slice(&unit.to_be_bytes());
+                    }
+                },
+                Self::Latin1 | Self::Windows1252 => {
+                    bytes.push(self.encode_8bit(character)?);
+                },
+            }
+        }
+
+        return Ok(bytes);
+    }
+
+    fn encode_8bit(self, character: char) -> Result<u8, Error> {
+        let high_char_index = WINDOWS_1252_HIGH_CHARS
+            .iter()
+            .position(
+                |high_char| {
+                    return *high_char == Some(character);
+                }
+            )
+            .filter(|_| { return self == Self::Windows1252; });
+
+        if let Some(index) = high_char_index {
+            return Ok(0x80_u8.saturating_add(u8::try_from(index).unwrap_or_default()));
+        }
+
+        return u8::try_from(character)
+            .ok()
+            .filter(
+                |byte| {
+                    return self == Self::Latin1 || !(0x80..0xA0).contains(byte);
+                }
+            )
+            .ok_or_else(
+                || {
+                    return Error::new(
+                        ErrorKind::InvalidData,
+                        format!("{character} cannot be encoded in {self}"),
+                    );
+                }
+            );
+    }
+
+    pub fn can_keep_raw_bytes_from(self, source: Self) -> bool {
+        return self.without_bom() == source.without_bom();
+    }
+
+    const fn without_bom(self) -> Self {
+        match self {
+            Self::Utf8Bom => {
+                return Self::Utf8;
+            },
+            Self::Utf16LeBom => {
+                return Self::Utf16Le;
+            },
+            Self::Utf16BeBom => {
+                return Self::Utf16Be;
+            },
+            Self::Utf8 | Self::Utf16Le | Self::Utf16Be | Self::Latin1 | Self::Windows1252 => {
+                return self;
+            },
+        }
+    }
+}
+
+impl Display for Encoding {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let label = match self {
+            Self::Utf8 => {
+                "UTF-8"
+            },
+            Self::Utf8Bom => {
+                "UTF-8 BOM"
+            },
+            Self::Utf16Le => {
+                "UTF-16 LE"
+            },
+            Self::Utf16LeBom => {
+                "UTF-16 LE BOM"
+            },
+            Self::Utf16Be => {
+                "UTF-16 BE"
+            },
+            Self::Utf16BeBom => {
+                "UTF-16 BE BOM"
+            },
+            Self::Latin1 => {
+                "ISO-8859-1"
+            },
+            Self::Windows1252 => {
+                "Windows-1252"
+            },
+        };
+
+        return write!(
+            formatter,
+            "{}",
+            label
+        );
+    }
+}
+
+impl TryFrom<&str> for Encoding {
+    type Error = String;
+
+    fn try_from(name: &str) -> Result<Self, Self::Error> {
+        let name = name.trim().to_lowercase();
+
+        return Self::NAMES
+            .iter()
+            .find(
+                |(_, encoding_name)| {
+                    return *encoding_name == name;
+                }
+            )
+            .map(
+                |(encoding, _)| {
+                    return *encoding;
+                }
+            )
+            .ok_or_else(
+                || {
+                    return format!("Unknown encoding: {name}");
+                }
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_fixture(encoding: Encoding) -> Vec<u8> {
+        match encoding {
+            Encoding::Utf8 => {
+                return b"caf\xC3\xA9 \xFF\xFE\n\xC3 end".to_vec();
+            },
+            Encoding::Utf8Bom => {
+                return b"\xEF\xBB\xBFcaf\xC3\xA9 \xFF\n".to_vec();
+            },
+            Encoding::Utf16Le => {
+                return b"h\0\xE9\0\0\xD8\n\0\x3D\xD8\x00\xDE!".to_vec();
+            },
+            Encoding::Utf16LeBom => {
+                return b"\xFF\xFEh\0\xE9\0\0\xD8\n\0!".to_vec();
+            },
+            Encoding::Utf16Be => {
+                return b"\0h\0\xE9\xD8\0\0\n\xD8\x3D\xDE\x00!".to_vec();
+            },
+            Encoding::Utf16BeBom => {
+                return b"\xFE\xFF\0h\0\xE9\xD8\0\0\n!".to_vec();
+            },
+            Encoding::Latin1 | Encoding::Windows1252 => {
+                return (0..=u8::MAX).collect();
+            },
+        }
+    }
+
+    #[test]
+    fn decode_and_encode_round_trip_every_encoding() {
+        for encoding in Encoding::ALL {
+            let bytes = get_fixture(encoding);
+            let (text, raw_bytes) = encoding.decode(&bytes);
+
+            assert_eq!(encoding.encode(&text, &raw_bytes).unwrap(), bytes, "{encoding}");
+        }
+    }
+
+    #[test]
+    fn decode_flags_invalid_bytes() {
+        let raw_byte = RawByte {
+            byte_index: 2,
+            value: 0xFF,
+        };
+
+        assert_eq!(Encoding::Utf8.decode(b"ok\xFF"), (String::from("ok\u{FFFD}"), vec![raw_byte]));
+        assert_eq!(Encoding::Utf8.decode("ok".as_bytes()), (String::from("ok"), Vec::new()));
+        assert_eq!(Encoding::Windows1252.decode(b"\x81").1.len(), 1);
+        assert!(Encoding::Windows1252.decode(b"\x80").1.is_empty());
+        assert!(Encoding::Latin1.decode(b"\x81").1.is_empty());
+    }
+
+    #[test]
+    fn real_characters_survive_next_to_raw_bytes() {
+        let bytes = "\u{FFFD}\u{10FFFF}".bytes().chain([0xFF]).collect::<Vec<u8>>();
+        let (text, raw_bytes) = Encoding::Utf8.decode(&bytes);
+
+        assert_eq!(text, "\u{FFFD}\u{10FFFF}\u{FFFD}");
+        assert_eq!(raw_bytes.len(), 1);
+        assert_eq!(Encoding::Utf8.encode(&text, &raw_bytes).unwrap(), bytes);
+        assert_eq!(Encoding::Utf8.encode(&text, &[]).unwrap(), "\u{FFFD}\u{10FFFF}\u{FFFD}".as_bytes());
+    }
+
+    #[test]
+    fn raw_bytes_only_stay_within_their_encoding() {
+        assert!(Encoding::Utf8Bom.can_keep_raw_bytes_from(Encoding::Utf8));
+        assert!(Encoding::Utf16BeBom.can_keep_raw_bytes_from(Encoding::Utf16Be));
+        assert!(!Encoding::Utf16Le.can_keep_raw_bytes_from(Encoding::Utf8));
+        assert!(!Encoding::Latin1.can_keep_raw_bytes_from(Encoding::Windows1252));
+    }
+
+    #[test]
+    fn encode_rejects_unrepresentable_characters() {
+        assert!(Encoding::Latin1.encode("€", &[]).is_err());
+        assert!(Encoding::Windows1252.encode("\u{81}", &[]).is_err());
+        assert_eq!(Encoding::Windows1252.encode("€", &[]).unwrap(), [0x80]);
+    }
+
+    #[test]
+    fn detect_uses_the_bom() {
+        assert_eq!(Encoding::detect(b"\xEF\xBB\xBFa", Encoding::Latin1), Encoding::Utf8Bom);
+        assert_eq!(Encoding::detect(b"\xFF\xFEa\0", Encoding::Latin1), Encoding::Utf16LeBom);
+        assert_eq!(Encoding::detect(b"\xFE\xFF\0a", Encoding::Latin1), Encoding::Utf16BeBom);
+    }
+
+    #[test]
+    fn detect_finds_utf16_without_a_bom() {
+        assert_eq!(Encoding::detect(b"h\0e\0l\0l\0o\0", Encoding::Latin1), Encoding::Utf16Le);
+        assert_eq!(Encoding::detect(b"\0h\0e\0l\0l\0o", Encoding::Latin1), Encoding::Utf16Be);
+        assert_eq!(Encoding::detect(b"hello\0", Encoding::Latin1), Encoding::Utf8);
+    }
+
+    #[test]
+    fn detect_falls_back_for_invalid_utf8() {
+        assert_eq!(Encoding::detect(b"caf\xE9", Encoding::Latin1), Encoding::Latin1);
+        assert_eq!(Encoding::detect("café".as_bytes(), Encoding::Latin1), Encoding::Utf8);
+        assert_eq!(Encoding::detect(b"", Encoding::Latin1), Encoding::Utf8);
+    }
+
+    #[test]
+    fn names_and_keys_map_back() {
+        for encoding in Encoding::ALL {
+            assert_eq!(Encoding::from_key(encoding.get_key()), Some(encoding));
+        }
+
+        for (encoding, name) in Encoding::NAMES {
+            assert_eq!(Encoding::try_from(name), Ok(encoding));
+        }
+
+        assert!(Encoding::try_from("ebcdic").is_err());
+    }
+}
@@ -1,8 +1,17 @@
+use super::{
+    LineEnding,
+    Encoding,
+};
+
 #[derive(Default, Eq, PartialEq, Debug)]
 pub struct FileStatus {
     pub lines_count: usize,
     pub current_line_index: usize,
     pub modified: bool,
+    pub overwrite_mode: bool,
+    pub line_ending: LineEnding,
+    pub missing_final_newline: bool,
+    pub encoding: Encoding,
     pub file_name: Option<String>,
 }
 
@@ -15,6 +24,29 @@ impl FileStatus {
         }
     }
 
+    pub fn edit_mode_to_string(&self) -> String {
+        if self.overwrite_mode {
+            return String::from("OVR");
+        } else {
+            return String::from("INS");
+        }
+    }
+
+    pub fn encoding_to_string(&self) -> String {
+        return self.encoding.to_string();
+    }
+
+    pub fn line_ending_to_string(&self) -> String {
+        if self.missing_final_newline {
+            return format!(
+                "{} noeol",
+                self.line_ending
+            );
+        } else {
+            return self.line_ending.to_string();
+        }
+    }
+
     pub fn lines_count_to_string(&self) -> String {
         if self.lines_count != 1 {
             return format!(
@@ -0,0 +1,125 @@
+use std::{
+    collections::BTreeMap,
+    fs::{
+        canonicalize,
+        read_to_string,
+        write,
+    },
+    io::{
+        Error,
+        ErrorKind,
+    },
+    path::{
+        Path,
+        PathBuf,
+    },
+};
+use super::{
+    DataDirectory,
+    Location,
+};
+
+const MARKS_FILE_NAME: &str = "marks";
+
+pub struct GlobalMarks;
+
+impl GlobalMarks {
+    pub fn get_all() -> Result<BTreeMap<char, (PathBuf, Location)>, Error> {
+        let contents = match read_to_string(DataDirectory::get_file_path(MARKS_FILE_NAME)?) {
+            Ok(contents) => {
+                contents
+            },
+            Err(error) if error.kind() == ErrorKind::NotFound => {
+                String::new()
+            },
+            Err(error) => {
+                return Err(error);
+            },
+        };
+
+        return Ok(
+            contents
+                .lines()
+                .filter_map(
+                    |line| {
+                        let mut parts = line.splitn(4, '\t');
+                        let name = parts.next()?.chars().next()?;
+                        let line_index = parts.next()?.parse().ok()?;
+                        let grapheme_index = parts.next()?.parse().ok()?;
+                        let path = PathBuf::from(parts.next()?);
+
+                        return Some(
+                            (
+                                name,
+                                (
+                                    path,
+                                    Location {
+                                        line_index,
+                                        grapheme_index,
+                                    },
+                                ),
+                            )
+                        );
+                    }
+                )
+                .collect()
+        );
+    }
+
+    pub fn get_for_file(file_path: &Path) -> Result<Vec<(char, Location)>, Error> {
+        let file_path = Self::get_key(file_path);
+
+        return Ok(
+            Self::get_all()?
+                .into_iter()
+                .filter(
+                    |(_, (path, _))| {
+                        return *path == file_path;
+                    }
+                )
+                .map(
+                    |(name, (_, location))| {
+                        return (name, location);
+                    }
+                )
+                .collect()
+        );
+    }
+
+    pub fn save_for_file(file_path: &Path, marks: &[(char, Location)]) -> Result<(), Error> {
+        if marks.is_empty() {
+            return Ok(());
+        }
+
+        let file_path = Self::get_key(file_path);
+        let mut saved = Self::get_all()?;
+
+        for (name, location) in marks {
+            saved.insert(*name, (file_path.clone(), *location));
+        }
+
+        let contents: String = saved
+            .iter()
+            .map(
+                |(name, (path, location))| {
+                    return format!(
+                        "{name}\t{}\t{}\t{}\n",
+                        location.line_index,
+                        location.grapheme_index,
+                        path.display(),
+                    );
+                }
+            )
+            .collect();
+
+        return write(DataDirectory::get_file_path(MARKS_FILE_NAME)?, contents);
+    }
+
+    pub fn get_key(file_path: &Path) -> PathBuf {
+        return canonicalize(file_path).unwrap_or_else(
+            |_| {
+                return file_path.to_path_buf();
+            }
+        );
+    }
+}
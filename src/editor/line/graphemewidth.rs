@@ -1,18 +1,30 @@
+use super::Column;
+
 #[derive(Copy, Clone, Debug)]
 pub enum GraphemeWidth {
     Half,
     Full,
+    Tab,
+    Escape,
 }
 
-impl From<GraphemeWidth> for usize {
-    fn from(val: GraphemeWidth) -> Self {
-        match val {
-            GraphemeWidth::Half => {
+impl GraphemeWidth {
+    pub fn get_columns(self, at_column: Column, tab_width: Column) -> Column {
+        match self {
+            Self::Half => {
                 return 1;
             },
-            GraphemeWidth::Full => {
+            Self::Full => {
                 return 2;
             },
+            Self::Escape => {
+                return 4;
+            },
+            Self::Tab => {
+                let tab_width = tab_width.max(1);
+
+                return tab_width.saturating_sub(at_column % tab_width);
+            },
         }
     }
 }
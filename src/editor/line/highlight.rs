@@ -0,0 +1,11 @@
+use std::ops::Range;
+use super::{
+    AnnotationType,
+    GraphemeIndex,
+};
+
+#[derive(Clone)]
+pub struct Highlight {
+    pub annotation_type: AnnotationType,
+    pub range: Range<GraphemeIndex>,
+}
@@ -1,7 +1,11 @@
 mod graphemewidth;
 mod textfragment;
+mod highlight;
+mod number;
 
 use std::{
+    cmp::min,
+    iter::once,
     ops::{
         Deref,
         Range,
@@ -13,12 +17,17 @@ use unicode_segmentation::UnicodeSegmentation;
 use unicode_width::UnicodeWidthStr;
 use graphemewidth::GraphemeWidth;
 use textfragment::TextFragment;
+use number::Number;
+pub use highlight::Highlight;
 use super::{
     AnnotatedString,
     AnnotationType,
-    Column
+    Column,
+    RawByte,
 };
 
+pub const DEFAULT_TAB_WIDTH: Column = 4;
+
 type GraphemeIndex = usize;
 type ByteIndex = usize;
 type ColumnIndex = usize;
@@ -27,22 +36,33 @@ type ColumnIndex = usize;
 pub struct Line {
     fragments: Vec<TextFragment>,
     string: String,
+    raw_bytes: Vec<RawByte>,
 }
 
 impl Line {
     pub fn from(line_str: &str) -> Self {
+        return Self::with_raw_bytes(line_str, Vec::new());
+    }
+
+    pub fn with_raw_bytes(line_str: &str, raw_bytes: Vec<RawByte>) -> Self {
         return Self {
-            fragments: Self::str_to_fragments(line_str),
+            fragments: Self::str_to_fragments(line_str, &raw_bytes),
             string: String::from(line_str),
+            raw_bytes,
         };
     }
 
-    fn str_to_fragments(line_str: &str) -> Vec<TextFragment> {
+    fn str_to_fragments(line_str: &str, raw_bytes: &[RawByte]) -> Vec<TextFragment> {
         return line_str
             .grapheme_indices(true)
             .map(
                 |(byte_index, grapheme)| {
-                    let (replacement, rendered_width) = Self::get_char_replacement(grapheme)
+                    let (replacement, rendered_width) = Self::get_raw_byte_replacement(raw_bytes, byte_index)
+                        .or_else(
+                            || {
+                                return Self::get_char_replacement(grapheme);
+                            }
+                        )
                         .map_or_else(
                             || {
                                 let unicode_width = grapheme.width();
@@ -61,9 +81,17 @@ impl Line {
                                 );
                             },
                             |replacement| {
+                                let rendered_width = if grapheme == "\t" {
+                                    GraphemeWidth::Tab
+                                } else if replacement.starts_with("\\x") {
+                                    GraphemeWidth::Escape
+                                } else {
+                                    GraphemeWidth::Half
+                                };
+
                                 return (
                                     Some(replacement),
-                                    GraphemeWidth::Half
+                                    rendered_width,
                                 );
                             },
                         );
@@ -80,10 +108,26 @@ impl Line {
     }
 
     fn rerender_fragments(&mut self) {
-        self.fragments = Self::str_to_fragments(&self.string);
+        self.fragments = Self::str_to_fragments(&self.string, &self.raw_bytes);
+    }
+
+    fn get_raw_byte_replacement(raw_bytes: &[RawByte], byte_index: ByteIndex) -> Option<String> {
+        return raw_bytes
+            .binary_search_by_key(
+                &byte_index,
+                |raw_byte| {
+                    return raw_byte.byte_index;
+                }
+            )
+            .ok()
+            .map(
+                |position| {
+                    return format!("\\x{:02X}", raw_bytes[position].value);
+                }
+            );
     }
 
-    fn get_char_replacement(for_str: &str) -> Option<char> {
+    fn get_char_replacement(for_str: &str) -> Option<String> {
         let width = for_str.width();
 
         match for_str {
@@ -91,21 +135,21 @@ impl Line {
                 return None;
             },
             "\t" => {
-                return Some(' ');
+                return Some(String::from(' '));
             },
             _ if width > 0 && for_str.trim().is_empty() => {
-                return Some('␣');
+                return Some(String::from('␣'));
             },
             _ if width == 0 => {
                 let mut chars = for_str.chars();
 
                 if let Some(c) = chars.next() {
                     if c.is_control() && chars.next().is_none() {
-                        return Some('▯');
+                        return Some(String::from('▯'));
                     }
                 }
 
-                return Some('·');
+                return Some(String::from('·'));
             },
             _ => {
                 return None;
@@ -113,11 +157,11 @@ impl Line {
         }
     }
 
-   pub fn get_visible_graphemes(&self, range: Range<ColumnIndex>) -> String {
-        return self.get_annotated_visible_substr(range, None, None).to_string()
+   pub fn get_visible_graphemes(&self, range: Range<ColumnIndex>, tab_width: Column) -> String {
+        return self.get_annotated_visible_substr(range, tab_width, None, None, &[]).to_string()
     }
 
-    pub fn get_annotated_visible_substr(&self, range: Range<ColumnIndex>, query: Option<&str>, selected_match: Option<GraphemeIndex>) -> AnnotatedString {
+    pub fn get_annotated_visible_substr(&self, range: Range<ColumnIndex>, tab_width: Column, query: Option<&str>, selected_match: Option<GraphemeIndex>, highlights: &[Highlight]) -> AnnotatedString {
         if range.start >= range.end {
             return AnnotatedString::default();
         }
@@ -150,12 +194,29 @@ impl Line {
             }
         }
 
-        let mut fragment_start = self.width();
+        let mut end_of_line_highlight = None;
+
+        for highlight in highlights {
+            let start_byte_index = self.byte_index_at(highlight.range.start);
+            let end_byte_index = self.byte_index_at(highlight.range.end);
+
+            if start_byte_index < end_byte_index {
+                result.add_annotation(
+                    highlight.annotation_type,
+                    start_byte_index,
+                    end_byte_index,
+                );
+            } else if highlight.range.start >= self.grapheme_count() && highlight.range.start < highlight.range.end {
+                end_of_line_highlight = Some(highlight.annotation_type);
+            }
+        }
 
-        for fragment in self.fragments.iter().rev() {
-            let fragment_end = fragment_start;
+        let columns = self.get_fragment_columns(tab_width);
+        let width = columns.last().copied().unwrap_or_default();
 
-            fragment_start = fragment_start.saturating_sub(fragment.rendered_width.into());
+        for (fragment_index, fragment) in self.fragments.iter().enumerate().rev() {
+            let fragment_start = columns[fragment_index];
+            let fragment_end = columns[fragment_index.saturating_add(1)];
 
             if fragment_start > range.end {
                 continue;
@@ -194,15 +255,26 @@ impl Line {
             }
 
             if fragment_start >= range.start && fragment_end <= range.end {
-                if let Some(replacement) = fragment.replacement {
+                if let Some(replacement) = &fragment.replacement {
                     let start_byte_index = fragment.start_byte_index;
                     let end_byte_index = start_byte_index.saturating_add(fragment.grapheme.len());
+                    let replacement = if matches!(fragment.rendered_width, GraphemeWidth::Tab) {
+                        replacement.repeat(fragment_end.saturating_sub(fragment_start))
+                    } else {
+                        replacement.clone()
+                    };
 
-                    result.replace(start_byte_index, end_byte_index, &replacement.to_string());
+                    result.replace(start_byte_index, end_byte_index, &replacement);
                 }
             }
         }
 
+        if let Some(annotation_type) = end_of_line_highlight
+            && width >= range.start
+            && width < range.end {
+            result.append(" ", annotation_type);
+        }
+
         return result;
     }
 
@@ -210,30 +282,103 @@ impl Line {
         return self.fragments.len();
     }
 
-    pub fn width_until(&self, grapheme_index: GraphemeIndex) -> Column {
+    pub fn get_leading_whitespace_count(&self) -> GraphemeIndex {
         return self.fragments
             .iter()
-            .take(grapheme_index)
-            .map(
-                |fragment| match fragment.rendered_width {
-                    GraphemeWidth::Half => {
-                        return 1;
-                    },
-                    GraphemeWidth::Full => {
-                        return 2;
-                    },
+            .take_while(
+                |fragment| {
+                    return fragment.grapheme.trim().is_empty();
                 }
             )
-            .sum()
+            .count();
     }
 
-    pub fn width(&self) -> Column {
-        return self.width_until(self.grapheme_count());
+    pub fn get_trailing_whitespace_start(&self) -> GraphemeIndex {
+        let trailing_whitespace_count = self.fragments
+            .iter()
+            .rev()
+            .take_while(
+                |fragment| {
+                    return fragment.grapheme.trim().is_empty();
+                }
+            )
+            .count();
+
+        return self.grapheme_count().saturating_sub(trailing_whitespace_count);
+    }
+
+    pub fn width_until(&self, grapheme_index: GraphemeIndex, tab_width: Column) -> Column {
+        return self
+            .iter_fragment_columns(tab_width)
+            .nth(min(grapheme_index, self.grapheme_count()))
+            .unwrap_or_default();
+    }
+
+    pub fn grapheme_index_at_column(&self, column: Column, tab_width: Column) -> GraphemeIndex {
+        return self
+            .iter_fragment_columns(tab_width)
+            .skip(1)
+            .position(
+                |fragment_end| {
+                    return fragment_end > column;
+                }
+            )
+            .unwrap_or_else(
+                || {
+                    return self.grapheme_count();
+                }
+            );
+    }
+
+    pub fn grapheme_index_from_column(&self, column: Column, tab_width: Column) -> GraphemeIndex {
+        return self
+            .iter_fragment_columns(tab_width)
+            .position(
+                |fragment_start| {
+                    return fragment_start >= column;
+                }
+            )
+            .unwrap_or_else(
+                || {
+                    return self.grapheme_count();
+                }
+            );
+    }
+
+    pub fn width(&self, tab_width: Column) -> Column {
+        return self.width_until(self.grapheme_count(), tab_width);
+    }
+
+    pub fn get_fragment_columns(&self, tab_width: Column) -> Vec<Column> {
+        return self.iter_fragment_columns(tab_width).collect();
+    }
+
+    fn iter_fragment_columns(&self, tab_width: Column) -> impl Iterator<Item = Column> {
+        return once(0).chain(
+            self.fragments
+                .iter()
+                .scan(
+                    0,
+                    move |column: &mut Column, fragment| {
+                        *column = column.saturating_add(fragment.rendered_width.get_columns(*column, tab_width));
+
+                        return Some(*column);
+                    }
+                )
+        );
     }
 
     pub fn insert_char(&mut self, character: char, at: GraphemeIndex) {
         if let Some(fragment) = self.fragments.get(at) {
-            self.string.insert(fragment.start_byte_index, character);
+            let byte_index = fragment.start_byte_index;
+
+            self.string.insert(byte_index, character);
+
+            for raw_byte in &mut self.raw_bytes {
+                if raw_byte.byte_index >= byte_index {
+                    *raw_byte = raw_byte.shifted_forward(character.len_utf8());
+                }
+            }
         } else {
             self.string.push(character);
         }
@@ -253,6 +398,18 @@ impl Line {
                 .saturating_add(fragment.grapheme.len());
 
             self.string.drain(start..end);
+            self.raw_bytes.retain(
+                |raw_byte| {
+                    return !(start..end).contains(&raw_byte.byte_index);
+                }
+            );
+
+            for raw_byte in &mut self.raw_bytes {
+                if raw_byte.byte_index >= end {
+                    *raw_byte = raw_byte.shifted_back(end.saturating_sub(start));
+                }
+            }
+
             self.rerender_fragments();
         }
     }
@@ -262,6 +419,17 @@ impl Line {
     }
 
     pub fn append(&mut self, other: &Self) {
+        let offset = self.string.len();
+
+        self.raw_bytes.extend(
+            other.raw_bytes
+                .iter()
+                .map(
+                    |raw_byte| {
+                        return raw_byte.shifted_forward(offset);
+                    }
+                )
+        );
         self.string.push_str(&other.string);
 
         self.rerender_fragments();
@@ -269,16 +437,184 @@ impl Line {
 
     pub fn split(&mut self, at: GraphemeIndex) -> Self {
         if let Some(fragment) = self.fragments.get(at) {
-            let remainder = self.string.split_off(fragment.start_byte_index);
+            let byte_index = fragment.start_byte_index;
+            let remainder = self.string.split_off(byte_index);
+            let remainder_raw_bytes = RawByte::get_in_range(&self.raw_bytes, byte_index..usize::MAX);
 
+            self.raw_bytes.retain(
+                |raw_byte| {
+                    return raw_byte.byte_index < byte_index;
+                }
+            );
             self.rerender_fragments();
 
-            return Self::from(&remainder);
+            return Self::with_raw_bytes(&remainder, remainder_raw_bytes);
         } else {
             return Self::default();
         }
     }
 
+    pub fn get_raw_bytes(&self, range: Range<GraphemeIndex>) -> Vec<RawByte> {
+        return RawByte::get_in_range(&self.raw_bytes, self.byte_index_at(range.start)..self.byte_index_at(range.end));
+    }
+
+    pub fn substr(&self, range: Range<GraphemeIndex>) -> &str {
+        let start_byte_index = self.byte_index_at(range.start);
+        let end_byte_index = self.byte_index_at(range.end);
+
+        return self.string
+            .get(start_byte_index..end_byte_index)
+            .unwrap_or_default();
+    }
+
+    pub fn next_word_end(&self, from_grapheme_index: GraphemeIndex, subword: bool) -> GraphemeIndex {
+        let from_byte_index = self.byte_index_at(from_grapheme_index);
+
+        return self
+            .get_word_segments(subword)
+            .into_iter()
+            .find(
+                |(byte_index, segment)| {
+                    return
+                        byte_index.saturating_add(segment.len()) > from_byte_index &&
+                        Self::is_word(segment);
+                }
+            )
+            .map_or(
+                self.grapheme_count(),
+                |(byte_index, segment)| {
+                    return self.grapheme_index_at_byte(byte_index.saturating_add(segment.len()));
+                }
+            );
+    }
+
+    pub fn previous_word_start(&self, from_grapheme_index: GraphemeIndex, subword: bool) -> GraphemeIndex {
+        let from_byte_index = self.byte_index_at(from_grapheme_index);
+
+        return self
+            .get_word_segments(subword)
+            .into_iter()
+            .rev()
+            .find(
+                |(byte_index, segment)| {
+                    return *byte_index < from_byte_index && Self::is_word(segment);
+                }
+            )
+            .map_or(
+                0,
+                |(byte_index, _)| {
+                    return self.grapheme_index_at_byte(byte_index);
+                }
+            );
+    }
+
+    pub fn get_word_range(&self, grapheme_index: GraphemeIndex, subword: bool) -> Option<Range<GraphemeIndex>> {
+        let byte_index = self.byte_index_at(grapheme_index);
+
+        return self
+            .get_word_segments(subword)
+            .into_iter()
+            .rev()
+            .find(
+                |(start_byte_index, segment)| {
+                    return Self::is_word(segment) && *start_byte_index <= byte_index && start_byte_index.saturating_add(segment.len()) >= byte_index;
+                }
+            )
+            .map(
+                |(start_byte_index, segment)| {
+                    return self.grapheme_index_at_byte(start_byte_index)..self.grapheme_index_at_byte(start_byte_index.saturating_add(segment.len()));
+                }
+            );
+    }
+
+    pub fn add_to_number(&self, from: GraphemeIndex, delta: i64) -> Option<(Range<GraphemeIndex>, String)> {
+        let number = Number::find(&self.string, self.byte_index_at(from))?;
+        let range = self.grapheme_index_at_byte(number.range.start)..self.grapheme_index_at_byte(number.range.end);
+
+        return Some((range, number.add(delta)?));
+    }
+
+    fn get_word_segments(&self, subword: bool) -> Vec<(ByteIndex, &str)> {
+        let segments = self.string.split_word_bound_indices();
+
+        if subword {
+            return segments
+                .flat_map(
+                    |(byte_index, segment)| {
+                        return Self::split_subwords(byte_index, segment);
+                    }
+                )
+                .collect();
+        }
+
+        return segments.collect();
+    }
+
+    fn split_subwords(offset: ByteIndex, segment: &str) -> Vec<(ByteIndex, &str)> {
+        let characters: Vec<(ByteIndex, char)> = segment.char_indices().collect();
+        let mut subwords = Vec::new();
+        let mut subword_start: Option<ByteIndex> = None;
+
+        for (index, &(byte_index, character)) in characters.iter().enumerate() {
+            if character == '_' {
+                if let Some(start) = subword_start.take() {
+                    subwords.push((offset.saturating_add(start), &segment[start..byte_index]));
+                }
+
+                continue;
+            }
+
+            let Some(start) = subword_start else {
+                subword_start = Some(byte_index);
+
+                continue;
+            };
+
+            let previous = characters[index.saturating_sub(1)].1;
+            let next = characters.get(index.saturating_add(1)).map(|(_, next)| { return *next; });
+            let is_boundary =
+                (previous.is_lowercase() && character.is_uppercase()) ||
+                (previous.is_uppercase() && character.is_uppercase() && next.is_some_and(char::is_lowercase)) ||
+                (previous.is_numeric() != character.is_numeric() && previous.is_alphanumeric() && character.is_alphanumeric());
+
+            if is_boundary {
+                subwords.push((offset.saturating_add(start), &segment[start..byte_index]));
+                subword_start = Some(byte_index);
+            }
+        }
+
+        if let Some(start) = subword_start {
+            subwords.push((offset.saturating_add(start), &segment[start..]));
+        }
+
+        return subwords;
+    }
+
+    fn is_word(segment: &str) -> bool {
+        return segment.chars().any(char::is_alphanumeric);
+    }
+
+    fn grapheme_index_at_byte(&self, byte_index: ByteIndex) -> GraphemeIndex {
+        return self
+            .byte_index_to_grapheme_index(byte_index)
+            .unwrap_or_else(
+                || {
+                    return self.grapheme_count();
+                }
+            );
+    }
+
+    fn byte_index_at(&self, grapheme_index: GraphemeIndex) -> ByteIndex {
+        return self.fragments
+            .get(grapheme_index)
+            .map_or(
+                self.string.len(),
+                |fragment| {
+                    return fragment.start_byte_index;
+                }
+            );
+    }
+
     fn byte_index_to_grapheme_index(&self, byte_index: ByteIndex) -> Option<GraphemeIndex> {
         if byte_index > self.string.len() {
             return None;
@@ -391,3 +727,71 @@ impl Deref for Line {
         return &self.string;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tabs_use_the_given_width() {
+        let line = Line::from("a\tb\t");
+
+        assert_eq!(line.get_fragment_columns(4), [0, 1, 4, 5, 8]);
+        assert_eq!(line.get_fragment_columns(8), [0, 1, 8, 9, 16]);
+        assert_eq!(line.width(2), 4);
+        assert_eq!(line.width_until(2, 8), 8);
+        assert_eq!(line.width_until(10, 8), 16);
+    }
+
+    #[test]
+    fn finds_graphemes_by_column() {
+        let line = Line::from("a\t界b");
+
+        assert_eq!(line.get_fragment_columns(4), [0, 1, 4, 6, 7]);
+        assert_eq!(line.grapheme_index_at_column(0, 4), 0);
+        assert_eq!(line.grapheme_index_at_column(2, 4), 1);
+        assert_eq!(line.grapheme_index_at_column(5, 4), 2);
+        assert_eq!(line.grapheme_index_at_column(9, 4), 4);
+        assert_eq!(line.grapheme_index_from_column(2, 4), 2);
+        assert_eq!(line.grapheme_index_from_column(5, 4), 3);
+        assert_eq!(line.grapheme_index_from_column(7, 4), 4);
+        assert_eq!(line.grapheme_index_from_column(9, 4), 4);
+    }
+
+    #[test]
+    fn renders_tabs_with_the_given_width() {
+        let line = Line::from("\tx");
+
+        assert_eq!(line.get_visible_graphemes(0..10, 2), "  x");
+        assert_eq!(line.get_visible_graphemes(0..10, 4), "    x");
+    }
+
+    #[test]
+    fn only_raw_bytes_are_rendered_as_escapes() {
+        let raw_byte = RawByte {
+            byte_index: 1,
+            value: 0xFF,
+        };
+        let mut line = Line::with_raw_bytes("a\u{FFFD}\u{FFFD}", vec![raw_byte]);
+
+        assert_eq!(line.get_visible_graphemes(0..10, 4), "a\\xFF\u{FFFD}");
+
+        line.insert_char('b', 0);
+        line.remove_char(3);
+
+        let mut remainder = line.split(1);
+
+        assert_eq!(remainder.get_visible_graphemes(0..10, 4), "a\\xFF");
+
+        remainder.append(&line);
+        remainder.append(&remainder.clone());
+        assert_eq!(remainder.get_visible_graphemes(0..20, 4), "a\\xFFba\\xFFb");
+        assert_eq!(
+            remainder.get_raw_bytes(1..5),
+            [
+                raw_byte.shifted_back(1),
+                raw_byte.shifted_forward(4),
+            ]
+        );
+    }
+}
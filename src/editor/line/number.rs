@@ -0,0 +1,194 @@
+use std::ops::Range;
+use super::ByteIndex;
+
+pub struct Number<'a> {
+    pub range: Range<ByteIndex>,
+    prefix: &'a str,
+    digits: &'a str,
+    radix: u32,
+    negative: bool,
+}
+
+impl<'a> Number<'a> {
+    pub fn find(text: &'a str, from: ByteIndex) -> Option<Self> {
+        let mut byte_index = 0;
+
+        while byte_index < text.len() {
+            if let Some(number) = Self::parse_at(text, byte_index) {
+                if number.range.end > from {
+                    return Some(number);
+                }
+
+                byte_index = number.range.end;
+            } else {
+                byte_index = byte_index.saturating_add(1);
+            }
+        }
+
+        return None;
+    }
+
+    fn parse_at(text: &'a str, start: ByteIndex) -> Option<Self> {
+        let bytes = text.as_bytes();
+        let byte_at = |index: ByteIndex| {
+            return bytes.get(index).copied().unwrap_or_default();
+        };
+
+        let radix = match (byte_at(start), byte_at(start.saturating_add(1))) {
+            (b'0', b'x' | b'X') => {
+                Some(16)
+            },
+            (b'0', b'b' | b'B') => {
+                Some(2)
+            },
+            _ => {
+                None
+            },
+        };
+
+        if let Some(radix) = radix {
+            let digits_start = start.saturating_add(2);
+            let digits_length = Self::count_digits(bytes.get(digits_start..).unwrap_or_default(), radix);
+
+            if digits_length > 0 {
+                let digits_end = digits_start.saturating_add(digits_length);
+
+                return Some(
+                    Self {
+                        range: start..digits_end,
+                        prefix: &text[start..digits_start],
+                        digits: &text[digits_start..digits_end],
+                        radix,
+                        negative: false,
+                    }
+                );
+            }
+        }
+
+        let is_word_before = start
+            .checked_sub(1)
+            .is_some_and(
+                |index| {
+                    return byte_at(index).is_ascii_alphanumeric() || byte_at(index) == b'_';
+                }
+            );
+        let negative = byte_at(start) == b'-' && !is_word_before;
+        let digits_start = if negative {
+            start.saturating_add(1)
+        } else {
+            start
+        };
+        let digits_length = Self::count_digits(bytes.get(digits_start..).unwrap_or_default(), 10);
+
+        if digits_length == 0 {
+            return None;
+        }
+
+        let digits_end = digits_start.saturating_add(digits_length);
+
+        return Some(
+            Self {
+                range: start..digits_end,
+                prefix: "",
+                digits: &text[digits_start..digits_end],
+                radix: 10,
+                negative,
+            }
+        );
+    }
+
+    fn count_digits(bytes: &[u8], radix: u32) -> usize {
+        return bytes
+            .iter()
+            .take_while(
+                |byte| {
+                    return char::from(**byte).is_digit(radix);
+                }
+            )
+            .count();
+    }
+
+    pub fn add(&self, delta: i64) -> Option<String> {
+        if self.radix == 10 {
+            let value = self.digits.parse::<i128>().ok()?;
+            let value = if self.negative {
+                -value
+            } else {
+                value
+            };
+            let new_value = value.checked_add(i128::from(delta))?;
+            let width = if self.digits.len() > 1 && self.digits.starts_with('0') {
+                self.digits.len()
+            } else {
+                0
+            };
+            let sign = if new_value < 0 {
+                "-"
+            } else {
+                ""
+            };
+
+            return Some(
+                format!(
+                    "{sign}{:0width$}",
+                    new_value.unsigned_abs()
+                )
+            );
+        }
+
+        let value = u64::from_str_radix(self.digits, self.radix).ok()?;
+        let new_value = value.wrapping_add_signed(delta);
+        let width = self.digits.len();
+        let is_upper_case = self.digits.chars().any(|character| { return character.is_ascii_uppercase(); }) &&
+            !self.digits.chars().any(|character| { return character.is_ascii_lowercase(); });
+        let digits = if self.radix == 16 && is_upper_case {
+            format!("{new_value:0width$X}")
+        } else if self.radix == 16 {
+            format!("{new_value:0width$x}")
+        } else {
+            format!("{new_value:0width$b}")
+        };
+
+        return Some(
+            format!(
+                "{}{digits}",
+                self.prefix
+            )
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn increment(text: &str, from: ByteIndex, delta: i64) -> Option<(Range<ByteIndex>, String)> {
+        let number = Number::find(text, from)?;
+
+        return Some((number.range.clone(), number.add(delta)?));
+    }
+
+    #[test]
+    fn finds_the_number_at_or_after_the_cursor() {
+        assert_eq!(increment("x = 41;", 0, 1), Some((4..6, String::from("42"))));
+        assert_eq!(increment("1 22", 2, 1), Some((2..4, String::from("23"))));
+        assert_eq!(increment("1 22", 4, 1), None);
+        assert_eq!(increment("none", 0, 1), None);
+    }
+
+    #[test]
+    fn keeps_signs_and_zero_padding() {
+        assert_eq!(increment("-1", 0, 2), Some((0..2, String::from("1"))));
+        assert_eq!(increment("9", 0, -10), Some((0..1, String::from("-1"))));
+        assert_eq!(increment("007", 0, 1), Some((0..3, String::from("008"))));
+        assert_eq!(increment("a-1", 0, 1), Some((2..3, String::from("2"))));
+    }
+
+    #[test]
+    fn keeps_the_radix_and_case() {
+        assert_eq!(increment("0x0F", 0, 1), Some((0..4, String::from("0x10"))));
+        assert_eq!(increment("0xff", 0, 1), Some((0..4, String::from("0x100"))));
+        assert_eq!(increment("0XAB", 0, 1), Some((0..4, String::from("0XAC"))));
+        assert_eq!(increment("0b101", 0, 1), Some((0..5, String::from("0b110"))));
+    }
+}
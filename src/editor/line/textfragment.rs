@@ -4,6 +4,6 @@ use super::GraphemeWidth;
 pub struct TextFragment {
     pub grapheme: String,
     pub rendered_width: GraphemeWidth,
-    pub replacement: Option<char>,
+    pub replacement: Option<String>,
     pub start_byte_index: usize,
 }
@@ -0,0 +1,76 @@
+use std::{
+    fmt,
+    fmt::Display,
+};
+
+#[derive(Default, Copy, Clone, Eq, PartialEq, Debug)]
+pub enum LineEnding {
+    #[default]
+    Lf,
+    Crlf,
+}
+
+impl LineEnding {
+    pub fn detect(data: &str) -> Self {
+        let crlf_count = data.matches("\r\n").count();
+        let lf_count = data.matches('\n').count().saturating_sub(crlf_count);
+
+        if crlf_count > lf_count {
+            return Self::Crlf;
+        } else {
+            return Self::Lf;
+        }
+    }
+
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Lf => {
+                return "\n";
+            },
+            Self::Crlf => {
+                return "\r\n";
+            },
+        }
+    }
+}
+
+impl Display for LineEnding {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Self::Lf => {
+                "LF"
+            },
+            Self::Crlf => {
+                "CRLF"
+            },
+        };
+
+        return write!(
+            formatter,
+            "{}",
+            name
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detect_picks_the_majority() {
+        assert_eq!(LineEnding::detect("a\r\nb\r\n"), LineEnding::Crlf);
+        assert_eq!(LineEnding::detect("a\r\nb\r\nc\n"), LineEnding::Crlf);
+        assert_eq!(LineEnding::detect("a\nb\r\nc\n"), LineEnding::Lf);
+        assert_eq!(LineEnding::detect("a\r\nb\n"), LineEnding::Lf);
+        assert_eq!(LineEnding::detect(""), LineEnding::Lf);
+    }
+
+    #[test]
+    fn as_str_and_display_match() {
+        assert_eq!(LineEnding::Lf.as_str(), "\n");
+        assert_eq!(LineEnding::Crlf.as_str(), "\r\n");
+        assert_eq!(LineEnding::Lf.to_string(), "LF");
+        assert_eq!(LineEnding::Crlf.to_string(), "CRLF");
+    }
+}
@@ -0,0 +1,32 @@
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum DiffLine {
+    Kept(String),
+    Removed(String),
+    Added(String),
+}
+
+impl DiffLine {
+    pub const fn get_key(&self) -> char {
+        match self {
+            Self::Kept(_) => {
+                return ' ';
+            },
+            Self::Removed(_) => {
+                return '-';
+            },
+            Self::Added(_) => {
+                return '+';
+            },
+        }
+    }
+
+    pub fn get_text(&self) -> &str {
+        match self {
+            Self::Kept(text)
+            | Self::Removed(text)
+            | Self::Added(text) => {
+                return text;
+            },
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use std::path::PathBuf;
+
+const MILLISECONDS_PER_SECOND: u64 = 1000;
+const SECONDS_PER_DAY: u64 = 86_400;
+const UNIX_EPOCH_YEAR: u64 = 1970;
+const MONTH_LENGTHS: [u64; 12] = [31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31];
+
+#[derive(Clone)]
+pub struct HistoryVersion {
+    pub path: PathBuf,
+    pub saved_at: u64,
+    pub size: u64,
+}
+
+impl HistoryVersion {
+    pub fn get_timestamp(&self) -> String {
+        let seconds = self.saved_at / MILLISECONDS_PER_SECOND;
+        let seconds_of_day = seconds % SECONDS_PER_DAY;
+        let mut days = seconds / SECONDS_PER_DAY;
+        let mut year = UNIX_EPOCH_YEAR;
+        let mut month = 0;
+
+        while days >= Self::get_year_length(year) {
+            days = days.saturating_sub(Self::get_year_length(year));
+            year = year.saturating_add(1);
+        }
+
+        while days >= Self::get_month_length(year, month) {
+            days = days.saturating_sub(Self::get_month_length(year, month));
+            month = month.saturating_add(1);
+        }
+
+        return format!(
+            "{year:04}-{:02}-{:02} {:02}:{:02}:{:02} UTC",
+            month.saturating_add(1),
+            days.saturating_add(1),
+            seconds_of_day / 3600,
+            seconds_of_day % 3600 / 60,
+            seconds_of_day % 60,
+        );
+    }
+
+    const fn is_leap_year(year: u64) -> bool {
+        return year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400));
+    }
+
+    const fn get_year_length(year: u64) -> u64 {
+        if Self::is_leap_year(year) {
+            return 366;
+        } else {
+            return 365;
+        }
+    }
+
+    const fn get_month_length(year: u64, month: usize) -> u64 {
+        if month == 1 && Self::is_leap_year(year) {
+            return 29;
+        } else {
+            return MONTH_LENGTHS[month];
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use std::cmp::max;
+use super::DiffLine;
+
+const MAX_COMPARED_CELLS: usize = 4_000_000;
+
+pub struct LineDiff;
+
+impl LineDiff {
+    pub fn diff(old_lines: &[String], new_lines: &[String]) -> Vec<DiffLine> {
+        let prefix_length = old_lines
+            .iter()
+            .zip(new_lines)
+            .take_while(
+                |(old_line, new_line)| {
+                    return old_line == new_line;
+                }
+            )
+            .count();
+        let suffix_length = old_lines[prefix_length..]
+            .iter()
+            .rev()
+            .zip(new_lines[prefix_length..].iter().rev())
+            .take_while(
+                |(old_line, new_line)| {
+                    return old_line == new_line;
+                }
+            )
+            .count();
+        let old_middle = &old_lines[prefix_length..old_lines.len().saturating_sub(suffix_length)];
+        let new_middle = &new_lines[prefix_length..new_lines.len().saturating_sub(suffix_length)];
+        let mut result: Vec<DiffLine> = old_lines[..prefix_length]
+            .iter()
+            .cloned()
+            .map(DiffLine::Kept)
+            .collect();
+
+        result.extend(Self::diff_middle(old_middle, new_middle));
+        result.extend(
+            old_lines[old_lines.len().saturating_sub(suffix_length)..]
+                .iter()
+                .cloned()
+                .map(DiffLine::Kept)
+        );
+
+        return result;
+    }
+
+    fn diff_middle(old_lines: &[String], new_lines: &[String]) -> Vec<DiffLine> {
+        let width = new_lines.len().saturating_add(1);
+
+        if old_lines.len().saturating_add(1).saturating_mul(width) > MAX_COMPARED_CELLS {
+            return old_lines
+                .iter()
+                .cloned()
+                .map(DiffLine::Removed)
+                .chain(new_lines.iter().cloned().map(DiffLine::Added))
+                .collect();
+        }
+
+        let get_cell = |old_index: usize, new_index: usize| {
+            return old_index.saturating_mul(width).saturating_add(new_index);
+        };
+        let mut common_lengths = vec![0_u32; get_cell(old_lines.len().saturating_add(1), 0)];
+
+        for old_index in (0..old_lines.len()).rev() {
+            for new_index in (0..new_lines.len()).rev() {
+                common_lengths[get_cell(old_index, new_index)] = if old_lines[old_index] == new_lines[new_index] {
+                    common_lengths[get_cell(old_index.saturating_add(1), new_index.saturating_add(1))].saturating_add(1)
+                } else {
+                    max(
+                        common_lengths[get_cell(old_index.saturating_add(1), new_index)],
+                        common_lengths[get_cell(old_index, new_index.saturating_add(1))],
+                    )
+                };
+            }
+        }
+
+        let mut result = Vec::new();
+        let mut old_index = 0;
+        let mut new_index = 0;
+
+        while old_index < old_lines.len() && new_index < new_lines.len() {
+            if old_lines[old_index] == new_lines[new_index] {
+                result.push(DiffLine::Kept(old_lines[old_index].clone()));
+                old_index = old_index.saturating_add(1);
+                new_index = new_index.saturating_add(1);
+            } else if common_lengths[get_cell(old_index.saturating_add(1), new_index)] >= common_lengths[get_cell(old_index, new_index.saturating_add(1))] {
+                result.push(DiffLine::Removed(old_lines[old_index].clone()));
+                old_index = old_index.saturating_add(1);
+            } else {
+                result.push(DiffLine::Added(new_lines[new_index].clone()));
+                new_index = new_index.saturating_add(1);
+            }
+        }
+
+        result.extend(old_lines[old_index..].iter().cloned().map(DiffLine::Removed));
+        result.extend(new_lines[new_index..].iter().cloned().map(DiffLine::Added));
+
+        return result;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diff(old_lines: &[&str], new_lines: &[&str]) -> Vec<DiffLine> {
+        let to_strings = |lines: &[&str]| {
+            return lines.iter().map(|line| { return String::from(*line); }).collect::<Vec<String>>();
+        };
+
+        return LineDiff::diff(&to_strings(old_lines), &to_strings(new_lines));
+    }
+
+    #[test]
+    fn keeps_identical_lines() {
+        assert_eq!(diff(&["a", "b"], &["a", "b"]), [DiffLine::Kept(String::from("a")), DiffLine::Kept(String::from("b"))]);
+        assert_eq!(diff(&[], &[]), []);
+    }
+
+    #[test]
+    fn marks_changed_lines() {
+        assert_eq!(
+            diff(&["a", "b", "c"], &["a", "x", "c"]),
+            [
+                DiffLine::Kept(String::from("a")),
+                DiffLine::Removed(String::from("b")),
+                DiffLine::Added(String::from("x")),
+                DiffLine::Kept(String::from("c")),
+            ]
+        );
+    }
+
+    #[test]
+    fn finds_the_longest_common_lines() {
+        assert_eq!(
+            diff(&["x", "a", "b"], &["a", "b", "y"]),
+            [
+                DiffLine::Removed(String::from("x")),
+                DiffLine::Kept(String::from("a")),
+                DiffLine::Kept(String::from("b")),
+                DiffLine::Added(String::from("y")),
+            ]
+        );
+        assert_eq!(diff(&[], &["a"]), [DiffLine::Added(String::from("a"))]);
+        assert_eq!(diff(&["a"], &[]), [DiffLine::Removed(String::from("a"))]);
+    }
+}
@@ -0,0 +1,241 @@
+mod historyversion;
+mod diffline;
+mod linediff;
+
+use std::{
+    cmp::Reverse,
+    fs::{
+        canonicalize,
+        read,
+        read_dir,
+        remove_file,
+        write,
+        OpenOptions,
+    },
+    io::{
+        Error,
+        ErrorKind,
+        Write,
+    },
+    path::{
+        Path,
+        PathBuf,
+    },
+    time::{
+        SystemTime,
+        UNIX_EPOCH,
+    },
+};
+use sha2::{
+    Digest,
+    Sha256,
+};
+use super::DataDirectory;
+pub use historyversion::HistoryVersion;
+pub use diffline::DiffLine;
+pub use linediff::LineDiff;
+
+const HISTORY_DIRECTORY_NAME: &str = "history";
+const PATH_FILE_NAME: &str = "path";
+const MILLISECONDS_PER_DAY: u64 = 86_400_000;
+
+#[derive(Default)]
+pub struct LocalHistory {
+    max_versions: usize,
+    max_age_days: u64,
+}
+
+impl LocalHistory {
+    pub const fn new(max_versions: usize, max_age_days: u64) -> Self {
+        return Self {
+            max_versions,
+            max_age_days,
+        };
+    }
+
+    pub fn read_current(&self, file_path: &Path) -> Result<Option<Vec<u8>>, Error> {
+        if self.max_versions == 0 {
+            return Ok(None);
+        }
+
+        match read(file_path) {
+            Ok(contents) => {
+                return Ok(Some(contents));
+            },
+            Err(error) if error.kind() == ErrorKind::NotFound => {
+                return Ok(None);
+            },
+            Err(error) => {
+                return Err(error);
+            },
+        }
+    }
+
+    pub fn save_version(&self, file_path: &Path, contents: &[u8]) -> Result<(), Error> {
+        if self.max_versions == 0 {
+            return Ok(());
+        }
+
+        let directory = Self::get_directory(file_path)?;
+        let versions = Self::get_versions_in(&directory)?;
+
+        Self::create_private_directory(directory.parent().unwrap_or(&directory))?;
+        Self::create_private_directory(&directory)?;
+        write(directory.join(PATH_FILE_NAME), Self::get_key(file_path).to_string_lossy().as_bytes())?;
+
+        let is_unchanged = versions
+            .first()
+            .is_some_and(
+                |latest| {
+                    return read(&latest.path).is_ok_and(|latest_contents| { return latest_contents == contents; });
+                }
+            );
+
+        if !is_unchanged {
+            Self::write_version(&directory, contents)?;
+        }
+
+        return self.prune(&directory);
+    }
+
+    pub fn get_versions(file_path: &Path) -> Result<Vec<HistoryVersion>, Error> {
+        return Self::get_versions_in(&Self::get_directory(file_path)?);
+    }
+
+    fn get_versions_in(directory: &Path) -> Result<Vec<HistoryVersion>, Error> {
+        let entries = match read_dir(directory) {
+            Ok(entries) => {
+                entries
+            },
+            Err(error) if error.kind() == ErrorKind::NotFound => {
+                return Ok(Vec::new());
+            },
+            Err(error) => {
+                return Err(error);
+            },
+        };
+        let mut versions: Vec<HistoryVersion> = entries
+            .filter_map(
+                |entry| {
+                    let entry = entry.ok()?;
+                    let saved_at = entry.file_name().to_str()?.parse().ok()?;
+
+                    return Some(
+                        HistoryVersion {
+                            path: entry.path(),
+                            saved_at,
+                            size: entry.metadata().ok()?.len(),
+                        }
+                    );
+                }
+            )
+            .collect();
+
+        versions.sort_by_key(
+            |version| {
+                return Reverse(version.saved_at);
+            }
+        );
+
+        return Ok(versions);
+    }
+
+    fn write_version(directory: &Path, contents: &[u8]) -> Result<(), Error> {
+        let mut saved_at = Self::get_current_time();
+
+        loop {
+            let mut options = OpenOptions::new();
+
+            options.write(true).create_new(true);
+            Self::set_private_mode(&mut options);
+
+            match options.open(directory.join(saved_at.to_string())) {
+                Ok(mut file) => {
+                    return file.write_all(contents);
+                },
+                Err(error) if error.kind() == ErrorKind::AlreadyExists => {
+                    saved_at = saved_at.saturating_add(1);
+                },
+                Err(error) => {
+                    return Err(error);
+                },
+            }
+        }
+    }
+
+    #[cfg(unix)]
+    fn create_private_directory(directory: &Path) -> Result<(), Error> {
+        use std::{
+            fs::{
+                set_permissions,
+                DirBuilder,
+                Permissions,
+            },
+            os::unix::fs::{
+                DirBuilderExt,
+                PermissionsExt,
+            },
+        };
+
+        DirBuilder::new().recursive(true).mode(0o700).create(directory)?;
+
+        return set_permissions(directory, Permissions::from_mode(0o700));
+    }
+
+    #[cfg(not(unix))]
+    fn create_private_directory(directory: &Path) -> Result<(), Error> {
+        return std::fs::create_dir_all(directory);
+    }
+
+    #[cfg(unix)]
+    fn set_private_mode(options: &mut OpenOptions) {
+        use std::os::unix::fs::OpenOptionsExt;
+
+        options.mode(0o600);
+    }
+
+    #[cfg(not(unix))]
+    fn set_private_mode(_options: &mut OpenOptions) {}
+
+    fn prune(&self, directory: &Path) -> Result<(), Error> {
+        let oldest_kept = if self.max_age_days == 0 {
+            0
+        } else {
+            Self::get_current_time().saturating_sub(self.max_age_days.saturating_mul(MILLISECONDS_PER_DAY))
+        };
+
+        for (index, version) in Self::get_versions_in(directory)?.into_iter().enumerate() {
+            if index >= self.max_versions || version.saved_at < oldest_kept {
+                remove_file(version.path)?;
+            }
+        }
+
+        return Ok(());
+    }
+
+    fn get_directory(file_path: &Path) -> Result<PathBuf, Error> {
+        let key = Self::get_key(file_path);
+        let hash = Sha256::digest(key.as_os_str().as_encoded_bytes());
+
+        return Ok(DataDirectory::get_file_path(HISTORY_DIRECTORY_NAME)?.join(format!("{hash:x}")));
+    }
+
+    fn get_key(file_path: &Path) -> PathBuf {
+        return canonicalize(file_path).unwrap_or_else(
+            |_| {
+                return file_path.to_path_buf();
+            }
+        );
+    }
+
+    fn get_current_time() -> u64 {
+        return SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(
+                0,
+                |duration| {
+                    return u64::try_from(duration.as_millis()).unwrap_or(u64::MAX);
+                }
+            );
+    }
+}
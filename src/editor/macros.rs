@@ -0,0 +1,240 @@
+use std::{
+    fs::{
+        read_to_string,
+        write,
+    },
+    io::{
+        Error,
+        ErrorKind,
+    },
+};
+use super::{
+    Command,
+    DataDirectory,
+};
+
+const MACROS_FILE_NAME: &str = "macros";
+pub const MAX_MACRO_REPEAT_COUNT: usize = 10_000;
+
+#[derive(Default)]
+pub struct Macros {
+    recording: Option<Vec<Command>>,
+    current: Vec<Command>,
+    is_playing: bool,
+}
+
+impl Macros {
+    pub fn start_recording(&mut self) {
+        self.recording = Some(Vec::new());
+    }
+
+    pub fn stop_recording(&mut self) -> usize {
+        if let Some(commands) = self.recording.take() {
+            self.current = commands;
+        }
+
+        return self.current.len();
+    }
+
+    pub const fn is_recording(&self) -> bool {
+        return self.recording.is_some();
+    }
+
+    pub fn record(&mut self, command: Command) {
+        if let Some(commands) = &mut self.recording {
+            commands.push(command);
+        }
+    }
+
+    pub fn get_current(&self) -> &[Command] {
+        return &self.current;
+    }
+
+    pub const fn is_playing(&self) -> bool {
+        return self.is_playing;
+    }
+
+    pub const fn set_playing(&mut self, value: bool) {
+        self.is_playing = value;
+    }
+
+    pub fn get_saved_names() -> Result<Vec<String>, Error> {
+        let mut names: Vec<String> = Self::read_saved()?
+            .iter()
+            .filter_map(
+                |line| {
+                    return line.split_once('\t').map(
+                        |(name, _)| {
+                            return String::from(name);
+                        }
+                    );
+                }
+            )
+            .collect();
+
+        names.sort();
+        names.dedup();
+
+        return Ok(names);
+    }
+
+    pub fn save(&self, name: &str) -> Result<(), Error> {
+        let mut lines = Self::read_saved()?;
+
+        Self::set_line(&mut lines, name, &self.current);
+
+        let contents: String = lines
+            .iter()
+            .map(
+                |line| {
+                    return format!("{line}\n");
+                }
+            )
+            .collect();
+
+        return write(DataDirectory::get_file_path(MACROS_FILE_NAME)?, contents);
+    }
+
+    pub fn load(&mut self, name: &str) -> Result<(), Error> {
+        self.current = Self::find_commands(&Self::read_saved()?, name)?;
+
+        return Ok(());
+    }
+
+    fn set_line(lines: &mut Vec<String>, name: &str, commands: &[Command]) {
+        let commands: Vec<String> = commands.iter().map(Command::to_string).collect();
+        let new_line = format!("{name}\t{}", commands.join(" "));
+
+        if let Some(index) = lines.iter().position(|line| { return Self::get_commands(line, name).is_some(); }) {
+            lines[index] = new_line;
+        } else {
+            lines.push(new_line);
+        }
+    }
+
+    fn find_commands(lines: &[String], name: &str) -> Result<Vec<Command>, Error> {
+        let Some(commands) = lines
+            .iter()
+            .find_map(
+                |line| {
+                    return Self::get_commands(line, name);
+                }
+            ) else {
+            return Err(Error::from(ErrorKind::NotFound));
+        };
+
+        return commands
+            .split_whitespace()
+            .map(Command::try_from)
+            .collect::<Result<Vec<Command>, String>>()
+            .map_err(
+                |error| {
+                    return Error::new(ErrorKind::InvalidData, error);
+                }
+            );
+    }
+
+    fn get_commands<'a>(line: &'a str, name: &str) -> Option<&'a str> {
+        return line
+            .split_once('\t')
+            .filter(
+                |(line_name, _)| {
+                    return *line_name == name;
+                }
+            )
+            .map(
+                |(_, commands)| {
+                    return commands;
+                }
+            );
+    }
+
+    fn read_saved() -> Result<Vec<String>, Error> {
+        let contents = match read_to_string(DataDirectory::get_file_path(MACROS_FILE_NAME)?) {
+            Ok(contents) => {
+                contents
+            },
+            Err(error) if error.kind() == ErrorKind::NotFound => {
+                String::new()
+            },
+            Err(error) => {
+                return Err(error);
+            },
+        };
+
+        return Ok(contents.lines().map(String::from).collect());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::commands::{
+        Edit,
+        LineOperation,
+        Move,
+        System,
+        Transform,
+    };
+
+    fn get_sample_commands() -> Vec<Command> {
+        let mut commands = vec![
+            Command::Edit(Edit::InsertCharacter(' ')),
+            Command::Edit(Edit::InsertCharacter('é')),
+            Command::Edit(Edit::InsertCharacter('\t')),
+            Command::Edit(Edit::InsertLine),
+            Command::Edit(Edit::Increment(-3)),
+            Command::Edit(Edit::IncrementSequence(2)),
+            Command::Edit(Edit::LineOperation(LineOperation::SortByColumn(2, Some(',')))),
+            Command::Edit(Edit::LineOperation(LineOperation::SortByColumn(1, None))),
+            Command::Edit(Edit::Undo),
+            Command::Move(Move::WordRight),
+            Command::Select(Move::EndOfLine),
+            Command::SelectBlock(Move::Down),
+            Command::System(System::Paste),
+        ];
+
+        commands.extend(
+            Transform::ALL.map(
+                |transform| {
+                    return Command::Edit(Edit::Transform(transform));
+                }
+            )
+        );
+
+        return commands;
+    }
+
+    #[test]
+    fn saved_commands_load_back_unchanged() {
+        let commands = get_sample_commands();
+        let mut lines = Vec::new();
+
+        Macros::set_line(&mut lines, "sample", &commands);
+
+        assert_eq!(lines.len(), 1);
+        assert!(Macros::find_commands(&lines, "sample").is_ok_and(|loaded| { return loaded == commands; }));
+    }
+
+    #[test]
+    fn saving_replaces_only_the_named_macro() {
+        let mut lines = vec![
+            String::from("broken\tedit:no-such-command"),
+            String::from("sample\tedit:undo"),
+            String::from("# not a macro"),
+        ];
+
+        Macros::set_line(&mut lines, "sample", &[Command::Edit(Edit::Redo)]);
+        Macros::set_line(&mut lines, "other", &[Command::Edit(Edit::Undo)]);
+
+        assert_eq!(lines, ["broken\tedit:no-such-command", "sample\tedit:redo", "# not a macro", "other\tedit:undo"]);
+    }
+
+    #[test]
+    fn loading_reports_missing_and_unknown_macros() {
+        let lines = vec![String::from("broken\tedit:undo edit:no-such-command")];
+
+        assert!(matches!(Macros::find_commands(&lines, "missing"), Err(error) if error.kind() == ErrorKind::NotFound));
+        assert!(matches!(Macros::find_commands(&lines, "broken"), Err(error) if error.kind() == ErrorKind::InvalidData));
+    }
+}
@@ -0,0 +1,41 @@
+use std::ops::Range;
+
+type ByteIndex = usize;
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct RawByte {
+    pub byte_index: ByteIndex,
+    pub value: u8,
+}
+
+impl RawByte {
+    pub const fn shifted_forward(self, by: ByteIndex) -> Self {
+        return Self {
+            byte_index: self.byte_index.saturating_add(by),
+            value: self.value,
+        };
+    }
+
+    pub const fn shifted_back(self, by: ByteIndex) -> Self {
+        return Self {
+            byte_index: self.byte_index.saturating_sub(by),
+            value: self.value,
+        };
+    }
+
+    pub fn get_in_range(raw_bytes: &[Self], range: Range<ByteIndex>) -> Vec<Self> {
+        return raw_bytes
+            .iter()
+            .filter(
+                |raw_byte| {
+                    return range.contains(&raw_byte.byte_index);
+                }
+            )
+            .map(
+                |raw_byte| {
+                    return raw_byte.shifted_back(range.start);
+                }
+            )
+            .collect();
+    }
+}
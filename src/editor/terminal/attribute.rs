@@ -46,6 +46,72 @@ impl From<AnnotationType> for Attribute {
                     ),
                 };
             },
+            AnnotationType::Selection => {
+                return Self {
+                    foreground: Some(
+                        Color::Rgb {
+                            r: 255,
+                            g: 255,
+                            b: 255,
+                        }
+                    ),
+                    background: Some(
+                        Color::Rgb {
+                            r: 50,
+                            g: 90,
+                            b: 160,
+                        }
+                    ),
+                };
+            },
+            AnnotationType::MatchingBracket => {
+                return Self {
+                    foreground: Some(
+                        Color::Rgb {
+                            r: 255,
+                            g: 215,
+                            b: 0,
+                        }
+                    ),
+                    background: Some(
+                        Color::Rgb {
+                            r: 80,
+                            g: 80,
+                            b: 80,
+                        }
+                    ),
+                };
+            },
+            AnnotationType::Cursor => {
+                return Self {
+                    foreground: Some(
+                        Color::Rgb {
+                            r: 0,
+                            g: 0,
+                            b: 0,
+                        }
+                    ),
+                    background: Some(
+                        Color::Rgb {
+                            r: 220,
+                            g: 220,
+                            b: 220,
+                        }
+                    ),
+                };
+            },
+            AnnotationType::Mark => {
+                return Self {
+                    foreground: Some(
+                        Color::Rgb {
+                            r: 100,
+                            g: 200,
+                            b: 220,
+                        }
+                    ),
+                    background: None,
+                };
+            },
         }
     }
 }
@@ -22,6 +22,7 @@ use crossterm::{
         MoveTo,
         Hide,
         Show,
+        SetCursorStyle,
     },
     style::{
         Attribute::{
@@ -33,6 +34,14 @@ use crossterm::{
         SetBackgroundColor,
         SetForegroundColor,
     },
+    event::{
+        EnableBracketedPaste,
+        DisableBracketedPaste,
+        KeyboardEnhancementFlags,
+        PushKeyboardEnhancementFlags,
+        PopKeyboardEnhancementFlags,
+    },
+    clipboard::CopyToClipboard,
     queue,
     Command,
 };
@@ -50,6 +59,8 @@ impl Terminal {
         enable_raw_mode()?;
 
         Self::enter_altscreen()?;
+        Self::enable_bracketed_paste()?;
+        Self::enable_key_disambiguation()?;
         Self::disable_line_wrap()?;
         Self::clear_all()?;
         Self::move_cursor_to(
@@ -64,8 +75,11 @@ impl Terminal {
     }
 
     pub fn kill() -> Result<(), Error> {
+        Self::disable_key_disambiguation()?;
+        Self::disable_bracketed_paste()?;
         Self::leave_altscreen()?;
         Self::enable_line_wrap()?;
+        Self::set_default_cursor_style()?;
         Self::show_cursor()?;
         Self::execute()?;
 
@@ -125,6 +139,18 @@ impl Terminal {
         return Ok(());
     }
 
+    pub fn set_default_cursor_style() -> Result<(), Error> {
+        Self::queue_cmd(SetCursorStyle::DefaultUserShape)?;
+
+        return Ok(());
+    }
+
+    pub fn set_overwrite_cursor_style() -> Result<(), Error> {
+        Self::queue_cmd(SetCursorStyle::SteadyUnderScore)?;
+
+        return Ok(());
+    }
+
     pub fn enable_line_wrap() -> Result<(), Error> {
         Self::queue_cmd(EnableLineWrap)?;
 
@@ -137,6 +163,38 @@ impl Terminal {
         return Ok(());
     }
 
+    pub fn enable_bracketed_paste() -> Result<(), Error> {
+        Self::queue_cmd(EnableBracketedPaste)?;
+
+        return Ok(());
+    }
+
+    pub fn disable_bracketed_paste() -> Result<(), Error> {
+        Self::queue_cmd(DisableBracketedPaste)?;
+
+        return Ok(());
+    }
+
+    pub fn enable_key_disambiguation() -> Result<(), Error> {
+        Self::queue_cmd(
+            PushKeyboardEnhancementFlags(KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES),
+        )?;
+
+        return Ok(());
+    }
+
+    pub fn disable_key_disambiguation() -> Result<(), Error> {
+        Self::queue_cmd(PopKeyboardEnhancementFlags)?;
+
+        return Ok(());
+    }
+
+    pub fn copy_to_clipboard(text: &str) -> Result<(), Error> {
+        Self::queue_cmd(CopyToClipboard::to_clipboard_from(text))?;
+
+        return Ok(());
+    }
+
     pub fn set_title(new_title: &str) -> Result<(), Error> {
         Self::queue_cmd(SetTitle(new_title))?;
 
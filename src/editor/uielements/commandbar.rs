@@ -5,6 +5,7 @@ use std::{
 use super::{
     super::{
         commands::Edit,
+        line::DEFAULT_TAB_WIDTH,
         Line,
         Size,
         Terminal,
@@ -30,8 +31,23 @@ impl CommandBar {
                 self.value.remove_last_char()
             },
             Edit::DeleteNext
+            | Edit::DeleteWordPrevious
+            | Edit::DeleteWordNext
+            | Edit::DuplicateLine
+            | Edit::DeleteLine
+            | Edit::MoveLineUp
+            | Edit::MoveLineDown
+            | Edit::JoinLines
+            | Edit::Reflow
+            | Edit::Dedent
+            | Edit::Transform(_)
+            | Edit::LineOperation(_)
+            | Edit::Increment(_)
+            | Edit::IncrementSequence(_)
             | Edit::InsertLine
-            | Edit::InsertTab => {},
+            | Edit::InsertTab
+            | Edit::Undo
+            | Edit::Redo => {},
         }
 
         self.set_needs_redraw(true);
@@ -76,13 +92,13 @@ impl UIElement for CommandBar {
 
     fn draw(&mut self, row: usize) -> Result<(), Error> {
         let value_area = self.size.width.saturating_sub(self.prompt.len());
-        let value_end = self.value.width();
+        let value_end = self.value.width(DEFAULT_TAB_WIDTH);
         let value_start = value_end.saturating_sub(value_area);
 
         let message = format!(
             "{}{}",
             self.prompt,
-            self.value.get_visible_graphemes(value_start..value_end)
+            self.value.get_visible_graphemes(value_start..value_end, DEFAULT_TAB_WIDTH)
         );
 
         let to_print = if message.len() <= self.size.width {
@@ -3,9 +3,17 @@ mod view;
 mod statusbar;
 mod hintbar;
 mod commandbar;
+mod picker;
 
 pub use uielement::UIElement;
-pub use view::View;
+pub use view::{
+    View,
+    Location,
+};
 pub use statusbar::StatusBar;
 pub use hintbar::HintBar;
 pub use commandbar::CommandBar;
+pub use picker::{
+    Picker,
+    PickerItem,
+};
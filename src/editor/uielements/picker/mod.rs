@@ -0,0 +1,140 @@
+mod pickeritem;
+
+use std::{
+    cmp::min,
+    io::Error,
+};
+use super::{
+    super::{
+        line::DEFAULT_TAB_WIDTH,
+        Line,
+        Size,
+        Terminal,
+    },
+    UIElement,
+};
+pub use pickeritem::PickerItem;
+
+#[derive(Default)]
+pub struct Picker {
+    title: String,
+    items: Vec<PickerItem>,
+    selected_index: usize,
+    scroll_offset: usize,
+    size: Size,
+    needs_redraw: bool,
+}
+
+impl Picker {
+    pub fn set_items(&mut self, title: &str, items: Vec<PickerItem>) {
+        self.title = String::from(title);
+        self.items = items;
+        self.selected_index = 0;
+        self.scroll_offset = 0;
+
+        self.set_needs_redraw(true);
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected_index = self.selected_index.saturating_sub(1);
+        self.scroll_selected_into_view();
+    }
+
+    pub fn select_next(&mut self) {
+        self.selected_index = min(
+            self.selected_index.saturating_add(1),
+            self.items.len().saturating_sub(1),
+        );
+        self.scroll_selected_into_view();
+    }
+
+    pub fn get_selected(&self) -> Option<&PickerItem> {
+        return self.items.get(self.selected_index);
+    }
+
+    pub fn get_by_key(&self, key: char) -> Option<&PickerItem> {
+        return self.items
+            .iter()
+            .find(
+                |item| {
+                    return item.key == key;
+                }
+            );
+    }
+
+    pub fn get_height(&self) -> usize {
+        return min(
+            self.items.len().saturating_add(1),
+            self.size.height,
+        );
+    }
+
+    fn get_visible_items_count(&self) -> usize {
+        return self.get_height().saturating_sub(1);
+    }
+
+    fn scroll_selected_into_view(&mut self) {
+        let visible_items_count = self.get_visible_items_count();
+
+        if self.selected_index < self.scroll_offset {
+            self.scroll_offset = self.selected_index;
+        } else if self.selected_index >= self.scroll_offset.saturating_add(visible_items_count) {
+            self.scroll_offset = self.selected_index
+                .saturating_sub(visible_items_count)
+                .saturating_add(1);
+        }
+
+        self.set_needs_redraw(true);
+    }
+}
+
+impl UIElement for Picker {
+    fn set_needs_redraw(&mut self, value: bool) {
+        self.needs_redraw = value;
+    }
+
+    fn get_needs_redraw(&self) -> bool {
+        return self.needs_redraw;
+    }
+
+    fn set_size(&mut self, new_size: Size) {
+        self.size = new_size;
+    }
+
+    fn draw(&mut self, row: usize) -> Result<(), Error> {
+        let width = self.size.width;
+
+        Terminal::print_inverted_line(
+            row,
+            &format!(
+                "[ {} ]",
+                self.title,
+            ),
+        )?;
+
+        for (offset, (index, item)) in self
+            .items
+            .iter()
+            .enumerate()
+            .skip(self.scroll_offset)
+            .take(self.get_visible_items_count())
+            .enumerate() {
+            let current_row = row.saturating_add(offset).saturating_add(1);
+            let text = Line::from(
+                &format!(
+                    " {}  {}",
+                    item.key,
+                    item.label,
+                )
+            ).get_visible_graphemes(0..width, DEFAULT_TAB_WIDTH);
+
+            if index == self.selected_index {
+                Terminal::print_inverted_line(current_row, &text)?;
+            } else {
+                Terminal::print_line(current_row, &text)?;
+            }
+        }
+
+        return Ok(());
+    }
+}
@@ -0,0 +1,4 @@
+pub struct PickerItem {
+    pub key: char,
+    pub label: String,
+}
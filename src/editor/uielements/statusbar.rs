@@ -43,13 +43,19 @@ impl UIElement for StatusBar {
         let lines_count = self.current_status.lines_count_to_string();
         let modified_indicator = self.current_status.modified_indicator_to_string();
         let position_indicator = self.current_status.position_indicator_to_string();
+        let edit_mode = self.current_status.edit_mode_to_string();
+        let encoding = self.current_status.encoding_to_string();
+        let line_ending = self.current_status.line_ending_to_string();
         let left = format!(
             "[ STATUS ] :: [ {} ] [ {} ]",
             file_name,
             modified_indicator,
         );
         let right = format!(
-            " [ {} ] [ {} ]",
+            " [ {} ] [ {} {} ] [ {} ] [ {} ]",
+            edit_mode,
+            encoding,
+            line_ending,
             position_indicator,
             lines_count,
         );
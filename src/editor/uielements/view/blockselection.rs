@@ -0,0 +1,7 @@
+use crate::editor::Position;
+
+#[derive(Copy, Clone)]
+pub struct BlockSelection {
+    pub anchor: Position,
+    pub head: Position,
+}
@@ -0,0 +1,279 @@
+use std::{
+    fs::{
+        metadata,
+        read_link,
+        remove_file,
+        rename,
+        File,
+        Metadata,
+        OpenOptions,
+    },
+    io::{
+        Error,
+        ErrorKind,
+        Write,
+    },
+    path::{
+        Path,
+        PathBuf,
+    },
+    process,
+};
+
+const MAX_SYMLINK_DEPTH: usize = 40;
+const MAX_TEMP_FILE_ATTEMPTS: usize = 100;
+
+pub struct AtomicFile;
+
+impl AtomicFile {
+    pub fn write(file_path: &Path, bytes: &[u8]) -> Result<(), Error> {
+        let target = Self::resolve_symlinks(file_path);
+        let original_metadata = metadata(&target).ok();
+        let (mut temp_file, temp_path) = Self::create_temp_file(&target, original_metadata.as_ref())?;
+
+        let result = Self::fill_temp_file(&mut temp_file, bytes, original_metadata.as_ref())
+            .and_then(
+                |()| {
+                    drop(temp_file);
+
+                    return rename(&temp_path, &target);
+                }
+            );
+
+        if result.is_err() {
+            remove_file(&temp_path).ok();
+
+            return result;
+        }
+
+        if let Ok(directory) = File::open(Self::get_directory(&target)) {
+            directory.sync_all().ok();
+        }
+
+        return Ok(());
+    }
+
+    fn resolve_symlinks(file_path: &Path) -> PathBuf {
+        let mut path = file_path.to_path_buf();
+
+        for _ in 0..MAX_SYMLINK_DEPTH {
+            let Ok(link_target) = read_link(&path) else {
+                break;
+            };
+
+            path = Self::get_directory(&path).join(link_target);
+        }
+
+        return path;
+    }
+
+    fn get_directory(file_path: &Path) -> &Path {
+        return file_path
+            .parent()
+            .filter(
+                |parent| {
+                    return !parent.as_os_str().is_empty();
+                }
+            )
+            .unwrap_or_else(
+                || {
+                    return Path::new(".");
+                }
+            );
+    }
+
+    fn create_temp_file(target: &Path, original_metadata: Option<&Metadata>) -> Result<(File, PathBuf), Error> {
+        let file_name = target
+            .file_name()
+            .ok_or_else(
+                || {
+                    return Error::new(ErrorKind::InvalidInput, "Not a file path");
+                }
+            )?
+            .to_string_lossy();
+        let mut last_error = Error::from(ErrorKind::AlreadyExists);
+
+        for attempt in 0..MAX_TEMP_FILE_ATTEMPTS {
+            let temp_path = Self::get_directory(target).join(format!(".{file_name}.rsedit-{}-{attempt}.tmp", process::id()));
+
+            let mut options = OpenOptions::new();
+
+            options.write(true).create_new(true);
+            Self::set_initial_mode(&mut options, original_metadata);
+
+            match options.open(&temp_path) {
+                Ok(file) => {
+                    return Ok((file, temp_path));
+                },
+                Err(error) if error.kind() == ErrorKind::AlreadyExists => {
+                    last_error = error;
+                },
+                Err(error) => {
+                    return Err(error);
+                },
+            }
+        }
+
+        return Err(last_error);
+    }
+
+    fn fill_temp_file(file: &mut File, bytes: &[u8], original_metadata: Option<&Metadata>) -> Result<(), Error> {
+        if let Some(original_metadata) = original_metadata {
+            Self::copy_owner(file, original_metadata);
+            file.set_permissions(original_metadata.permissions())?;
+        }
+
+        file.write_all(bytes)?;
+
+        return file.sync_all();
+    }
+
+    #[cfg(unix)]
+    fn set_initial_mode(options: &mut OpenOptions, original_metadata: Option<&Metadata>) {
+        use std::os::unix::fs::{
+            OpenOptionsExt,
+            PermissionsExt,
+        };
+
+        if let Some(original_metadata) = original_metadata {
+            options.mode(original_metadata.permissions().mode() & 0o777);
+        }
+    }
+
+    #[cfg(not(unix))]
+    fn set_initial_mode(_options: &mut OpenOptions, _original_metadata: Option<&Metadata>) {}
+
+    #[cfg(unix)]
+    fn copy_owner(file: &File, original_metadata: &Metadata) {
+        use std::os::unix::fs::{
+            fchown,
+            MetadataExt,
+        };
+
+        if fchown(file, Some(original_metadata.uid()), Some(original_metadata.gid())).is_err() {
+            fchown(file, None, Some(original_metadata.gid())).ok();
+        }
+    }
+
+    #[cfg(not(unix))]
+    fn copy_owner(_file: &File, _original_metadata: &Metadata) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        env::temp_dir,
+        fs::{
+            create_dir,
+            create_dir_all,
+            read,
+            read_dir,
+            remove_dir_all,
+            write,
+        },
+    };
+    use super::*;
+
+    struct TestDirectory {
+        path: PathBuf,
+    }
+
+    impl TestDirectory {
+        fn new(name: &str) -> Self {
+            let path = temp_dir().join(format!("rsedit-atomicfile-{}-{name}", process::id()));
+
+            remove_dir_all(&path).ok();
+            create_dir_all(&path).unwrap();
+
+            return Self {
+                path,
+            };
+        }
+
+        fn get_entries(&self) -> Vec<String> {
+            let mut entries = read_dir(&self.path)
+                .unwrap()
+                .map(
+                    |entry| {
+                        return entry.unwrap().file_name().to_string_lossy().into_owned();
+                    }
+                )
+                .collect::<Vec<String>>();
+
+            entries.sort();
+
+            return entries;
+        }
+    }
+
+    impl Drop for TestDirectory {
+        fn drop(&mut self) {
+            remove_dir_all(&self.path).ok();
+        }
+    }
+
+    #[test]
+    fn replaces_the_contents_without_leaving_temp_files() {
+        let directory = TestDirectory::new("replace");
+        let file_path = directory.path.join("file.txt");
+
+        AtomicFile::write(&file_path, b"new file").unwrap();
+        assert_eq!(read(&file_path).unwrap(), b"new file");
+
+        AtomicFile::write(&file_path, b"replaced").unwrap();
+        assert_eq!(read(&file_path).unwrap(), b"replaced");
+        assert_eq!(directory.get_entries(), ["file.txt"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn keeps_the_original_mode() {
+        use std::{
+            fs::{
+                set_permissions,
+                Permissions,
+            },
+            os::unix::fs::PermissionsExt,
+        };
+
+        let directory = TestDirectory::new("mode");
+        let file_path = directory.path.join("script.sh");
+
+        write(&file_path, b"old").unwrap();
+        set_permissions(&file_path, Permissions::from_mode(0o750)).unwrap();
+
+        AtomicFile::write(&file_path, b"new").unwrap();
+
+        assert_eq!(metadata(&file_path).unwrap().permissions().mode() & 0o777, 0o750);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn writes_through_symlinks_to_the_target() {
+        use std::os::unix::fs::symlink;
+
+        let directory = TestDirectory::new("symlink");
+        let target_path = directory.path.join("target.txt");
+        let link_path = directory.path.join("link.txt");
+
+        write(&target_path, b"old").unwrap();
+        symlink("target.txt", &link_path).unwrap();
+
+        AtomicFile::write(&link_path, b"new").unwrap();
+
+        assert!(link_path.symlink_metadata().unwrap().file_type().is_symlink());
+        assert_eq!(read(&target_path).unwrap(), b"new");
+        assert_eq!(directory.get_entries(), ["link.txt", "target.txt"]);
+    }
+
+    #[test]
+    fn removes_the_temp_file_when_the_rename_fails() {
+        let directory = TestDirectory::new("failure");
+        let file_path = directory.path.join("occupied");
+
+        create_dir(&file_path).unwrap();
+
+        assert!(AtomicFile::write(&file_path, b"contents").is_err());
+        assert_eq!(directory.get_entries(), ["occupied"]);
+    }
+}
@@ -0,0 +1,128 @@
+use super::{
+    ChangeKind,
+    Location,
+    RawByte,
+};
+
+#[derive(Clone)]
+pub struct Change {
+    pub kind: ChangeKind,
+    pub start: Location,
+    pub end: Location,
+    pub text: String,
+    pub raw_bytes: Vec<RawByte>,
+}
+
+impl Change {
+    pub fn is_single_grapheme(&self) -> bool {
+        return
+            self.start.line_index == self.end.line_index &&
+            self.end.grapheme_index.saturating_sub(self.start.grapheme_index) == 1;
+    }
+
+    pub fn transform(&self, location: Location) -> Location {
+        match self.kind {
+            ChangeKind::Insert => {
+                if location < self.start {
+                    return location;
+                }
+
+                if location.line_index == self.start.line_index {
+                    return Location {
+                        line_index: self.end.line_index,
+                        grapheme_index: self.end.grapheme_index.saturating_add(
+                            location.grapheme_index.saturating_sub(self.start.grapheme_index)
+                        ),
+                    };
+                }
+
+                return Location {
+                    line_index: location.line_index.saturating_add(
+                        self.end.line_index.saturating_sub(self.start.line_index)
+                    ),
+                    grapheme_index: location.grapheme_index,
+                };
+            },
+            ChangeKind::Remove => {
+                if location <= self.start {
+                    return location;
+                }
+
+                if location <= self.end {
+                    return self.start;
+                }
+
+                if location.line_index == self.end.line_index {
+                    return Location {
+                        line_index: self.start.line_index,
+                        grapheme_index: self.start.grapheme_index.saturating_add(
+                            location.grapheme_index.saturating_sub(self.end.grapheme_index)
+                        ),
+                    };
+                }
+
+                return Location {
+                    line_index: location.line_index.saturating_sub(
+                        self.end.line_index.saturating_sub(self.start.line_index)
+                    ),
+                    grapheme_index: location.grapheme_index,
+                };
+            },
+        }
+    }
+
+    pub fn is_whitespace(&self) -> bool {
+        return self.text.chars().all(char::is_whitespace);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(line_index: usize, grapheme_index: usize) -> Location {
+        return Location {
+            grapheme_index,
+            line_index,
+        };
+    }
+
+    fn transform(kind: ChangeKind, start: Location, end: Location, location: Location) -> (usize, usize) {
+        let change = Change {
+            kind,
+            start,
+            end,
+            text: String::new(),
+            raw_bytes: Vec::new(),
+        };
+        let location = change.transform(location);
+
+        return (location.line_index, location.grapheme_index);
+    }
+
+    #[test]
+    fn inserts_shift_only_locations_at_or_after_them() {
+        let mark = at(1, 5);
+
+        assert_eq!(transform(ChangeKind::Insert, at(1, 2), at(1, 4), mark), (1, 7));
+        assert_eq!(transform(ChangeKind::Insert, at(1, 5), at(1, 6), mark), (1, 6));
+        assert_eq!(transform(ChangeKind::Insert, at(1, 2), at(3, 1), mark), (3, 4));
+        assert_eq!(transform(ChangeKind::Insert, at(0, 3), at(2, 0), mark), (3, 5));
+        assert_eq!(transform(ChangeKind::Insert, at(1, 6), at(1, 9), mark), (1, 5));
+        assert_eq!(transform(ChangeKind::Insert, at(2, 0), at(4, 0), mark), (1, 5));
+    }
+
+    #[test]
+    fn removals_pull_locations_back_or_to_their_start() {
+        let mark = at(1, 5);
+
+        assert_eq!(transform(ChangeKind::Remove, at(1, 0), at(1, 2), mark), (1, 3));
+        assert_eq!(transform(ChangeKind::Remove, at(0, 2), at(1, 1), mark), (0, 6));
+        assert_eq!(transform(ChangeKind::Remove, at(0, 0), at(1, 0), mark), (0, 5));
+        assert_eq!(transform(ChangeKind::Remove, at(1, 3), at(1, 7), mark), (1, 3));
+        assert_eq!(transform(ChangeKind::Remove, at(1, 3), at(1, 5), mark), (1, 3));
+        assert_eq!(transform(ChangeKind::Remove, at(0, 1), at(2, 0), mark), (0, 1));
+        assert_eq!(transform(ChangeKind::Remove, at(1, 5), at(1, 8), mark), (1, 5));
+        assert_eq!(transform(ChangeKind::Remove, at(2, 0), at(3, 0), mark), (1, 5));
+    }
+}
@@ -0,0 +1,5 @@
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub enum ChangeKind {
+    Insert,
+    Remove,
+}
@@ -0,0 +1,232 @@
+use std::mem::take;
+use super::{
+    Change,
+    Location,
+    Transaction,
+};
+
+pub struct History {
+    undo_stack: Vec<Transaction>,
+    redo_stack: Vec<Transaction>,
+    pending: Vec<Change>,
+    saved_depth: Option<usize>,
+    sealed: bool,
+}
+
+impl Default for History {
+    fn default() -> Self {
+        return Self {
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            pending: Vec::new(),
+            saved_depth: Some(0),
+            sealed: false,
+        };
+    }
+}
+
+impl History {
+    pub fn record(&mut self, change: Change) {
+        self.pending.push(change);
+    }
+
+    pub fn get_pending(&self) -> &[Change] {
+        return &self.pending;
+    }
+
+    pub fn commit(&mut self, location_before: Location, location_after: Location) {
+        if self.pending.is_empty() {
+            return;
+        }
+
+        let transaction = Transaction {
+            changes: take(&mut self.pending),
+            location_before,
+            location_after,
+        };
+
+        self.redo_stack.clear();
+
+        if self.saved_depth.is_some_and(|depth| { return depth > self.undo_stack.len(); }) {
+            self.saved_depth = None;
+        }
+
+        match self.undo_stack.last_mut() {
+            Some(last) if !self.sealed && last.can_absorb(&transaction) => {
+                last.absorb(transaction);
+            },
+            _ => {
+                self.undo_stack.push(transaction);
+            },
+        }
+
+        self.sealed = false;
+    }
+
+    pub fn take_undo(&mut self) -> Option<&Transaction> {
+        let transaction = self.undo_stack.pop()?;
+
+        self.sealed = true;
+        self.redo_stack.push(transaction);
+
+        return self.redo_stack.last();
+    }
+
+    pub fn take_redo(&mut self) -> Option<&Transaction> {
+        let transaction = self.redo_stack.pop()?;
+
+        self.sealed = true;
+        self.undo_stack.push(transaction);
+
+        return self.undo_stack.last();
+    }
+
+    pub fn seal(&mut self) {
+        self.sealed = true;
+    }
+
+    pub fn mark_saved(&mut self) {
+        self.saved_depth = Some(self.undo_stack.len());
+        self.seal();
+    }
+
+    pub const fn forget_save_point(&mut self) {
+        self.saved_depth = None;
+    }
+
+    pub fn is_at_save_point(&self) -> bool {
+        return self.saved_depth == Some(self.undo_stack.len());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::ChangeKind;
+
+    fn at(grapheme_index: usize) -> Location {
+        return Location {
+            grapheme_index,
+            line_index: 0,
+        };
+    }
+
+    fn type_text(history: &mut History, start: usize, text: &str) {
+        for (offset, character) in text.chars().enumerate() {
+            let grapheme_index = start.saturating_add(offset);
+
+            history.record(
+                Change {
+                    kind: ChangeKind::Insert,
+                    start: at(grapheme_index),
+                    end: at(grapheme_index.saturating_add(1)),
+                    text: String::from(character),
+                    raw_bytes: Vec::new(),
+                }
+            );
+            history.commit(at(grapheme_index), at(grapheme_index.saturating_add(1)));
+        }
+    }
+
+    fn count_undo_steps(history: &mut History) -> usize {
+        let mut steps: usize = 0;
+
+        while history.take_undo().is_some() {
+            steps = steps.saturating_add(1);
+        }
+
+        return steps;
+    }
+
+    #[test]
+    fn groups_typed_characters_into_words() {
+        let mut history = History::default();
+
+        type_text(&mut history, 0, "one two");
+
+        let transaction = history.take_undo().unwrap();
+
+        assert_eq!(transaction.changes.len(), 3);
+        assert!(transaction.location_before == at(4) && transaction.location_after == at(7));
+        assert_eq!(count_undo_steps(&mut history), 1);
+    }
+
+    #[test]
+    fn does_not_group_across_a_seal_or_a_jump() {
+        let mut history = History::default();
+
+        type_text(&mut history, 0, "ab");
+        history.seal();
+        type_text(&mut history, 2, "c");
+        type_text(&mut history, 10, "d");
+
+        assert_eq!(count_undo_steps(&mut history), 3);
+    }
+
+    #[test]
+    fn groups_consecutive_backspaces() {
+        let mut history = History::default();
+
+        for grapheme_index in (0..3).rev() {
+            history.record(
+                Change {
+                    kind: ChangeKind::Remove,
+                    start: at(grapheme_index),
+                    end: at(grapheme_index.saturating_add(1)),
+                    text: String::from("x"),
+                    raw_bytes: Vec::new(),
+                }
+            );
+            history.commit(at(grapheme_index.saturating_add(1)), at(grapheme_index));
+        }
+
+        assert_eq!(history.take_undo().unwrap().changes.len(), 3);
+    }
+
+    #[test]
+    fn ignores_commits_without_changes() {
+        let mut history = History::default();
+
+        history.commit(at(0), at(1));
+
+        assert!(history.take_undo().is_none());
+        assert!(history.is_at_save_point());
+    }
+
+    #[test]
+    fn tracks_the_save_point_through_undo_and_redo() {
+        let mut history = History::default();
+
+        type_text(&mut history, 0, "a");
+        history.mark_saved();
+        type_text(&mut history, 1, "b");
+        assert!(!history.is_at_save_point());
+
+        history.take_undo();
+        assert!(history.is_at_save_point());
+
+        history.take_undo();
+        assert!(!history.is_at_save_point());
+
+        history.take_redo();
+        assert!(history.is_at_save_point());
+
+        history.take_redo();
+        assert!(!history.is_at_save_point());
+    }
+
+    #[test]
+    fn forgets_the_save_point_when_its_redo_branch_is_dropped() {
+        let mut history = History::default();
+
+        type_text(&mut history, 0, "a");
+        history.mark_saved();
+        history.take_undo();
+        type_text(&mut history, 0, "b");
+        history.take_undo();
+
+        assert!(!history.is_at_save_point());
+        assert!(history.take_redo().is_some());
+        assert!(history.take_redo().is_none());
+    }
+}
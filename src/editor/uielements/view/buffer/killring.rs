@@ -0,0 +1,60 @@
+use std::collections::VecDeque;
+use crate::editor::ClipboardEntry;
+
+const MAX_ENTRIES: usize = 60;
+
+#[derive(Default)]
+pub struct KillRing {
+    entries: VecDeque<ClipboardEntry>,
+    yank_index: usize,
+}
+
+impl KillRing {
+    pub fn push(&mut self, entry: ClipboardEntry) {
+        if entry.text.is_empty() {
+            return;
+        }
+
+        self.entries.push_front(entry);
+        self.entries.truncate(MAX_ENTRIES);
+        self.yank_index = 0;
+    }
+
+    pub fn append(&mut self, text: &str, backward: bool) {
+        if let Some(head) = self.entries.front_mut() {
+            if backward {
+                head.text.insert_str(0, text);
+            } else {
+                head.text.push_str(text);
+            }
+
+            head.linewise = false;
+            head.blockwise = false;
+            self.yank_index = 0;
+        } else {
+            self.push(ClipboardEntry::from(text));
+        }
+    }
+
+    pub fn head(&self) -> Option<&ClipboardEntry> {
+        return self.entries.front();
+    }
+
+    pub fn rotate(&mut self) -> Option<&ClipboardEntry> {
+        if self.entries.is_empty() {
+            return None;
+        }
+
+        self.yank_index = self.yank_index.saturating_add(1) % self.entries.len();
+
+        return self.entries.get(self.yank_index);
+    }
+
+    pub fn reset_yank_index(&mut self) {
+        self.yank_index = 0;
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &ClipboardEntry> {
+        return self.entries.iter();
+    }
+}
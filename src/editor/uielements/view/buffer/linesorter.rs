@@ -0,0 +1,229 @@
+use std::{
+    cmp::Ordering,
+    collections::{
+        HashSet,
+        hash_map::RandomState,
+    },
+    hash::BuildHasher,
+};
+use super::LineOperation;
+
+pub struct LineSorter;
+
+impl LineSorter {
+    pub fn apply(operation: LineOperation, lines: &mut Vec<String>) {
+        match operation {
+            LineOperation::SortLexical => {
+                lines.sort();
+            },
+            LineOperation::SortCaseInsensitive => {
+                lines.sort_by_cached_key(
+                    |line| {
+                        return line.to_lowercase();
+                    }
+                );
+            },
+            LineOperation::SortNatural => {
+                lines.sort_by(
+                    |left, right| {
+                        return Self::compare_natural(left, right);
+                    }
+                );
+            },
+            LineOperation::SortNumeric => {
+                lines.sort_by(
+                    |left, right| {
+                        return Self::compare_numeric(left, right);
+                    }
+                );
+            },
+            LineOperation::SortByColumn(column, delimiter) => {
+                lines.sort_by(
+                    |left, right| {
+                        return Self::compare_natural(
+                            Self::get_column(left, column, delimiter),
+                            Self::get_column(right, column, delimiter),
+                        );
+                    }
+                );
+            },
+            LineOperation::Reverse => {
+                lines.reverse();
+            },
+            LineOperation::Unique => {
+                let mut seen = HashSet::new();
+
+                lines.retain(
+                    |line| {
+                        return seen.insert(line.clone());
+                    }
+                );
+            },
+            LineOperation::Shuffle => {
+                let random_state = RandomState::new();
+
+                for index in (1..lines.len()).rev() {
+                    let random = usize::try_from(random_state.hash_one(index)).unwrap_or(index);
+
+                    lines.swap(index, random % index.saturating_add(1));
+                }
+            },
+        }
+    }
+
+    fn compare_natural(left: &str, right: &str) -> Ordering {
+        let mut left_chunks = Self::split_chunks(left);
+        let mut right_chunks = Self::split_chunks(right);
+
+        loop {
+            match (left_chunks.next(), right_chunks.next()) {
+                (Some(left_chunk), Some(right_chunk)) => {
+                    let ordering = if Self::is_number(left_chunk) && Self::is_number(right_chunk) {
+                        Self::compare_digits(left_chunk, right_chunk)
+                    } else {
+                        left_chunk.cmp(right_chunk)
+                    };
+
+                    if ordering != Ordering::Equal {
+                        return ordering;
+                    }
+                },
+                (Some(_), None) => {
+                    return Ordering::Greater;
+                },
+                (None, Some(_)) => {
+                    return Ordering::Less;
+                },
+                (None, None) => {
+                    return left.cmp(right);
+                },
+            }
+        }
+    }
+
+    fn split_chunks(text: &str) -> impl Iterator<Item = &str> {
+        let mut remainder = text;
+
+        return std::iter::from_fn(
+            move || {
+                let first = remainder.chars().next()?;
+                let length = remainder
+                    .find(
+                        |character: char| {
+                            return character.is_ascii_digit() != first.is_ascii_digit();
+                        }
+                    )
+                    .unwrap_or(remainder.len());
+                let (chunk, rest) = remainder.split_at(length);
+
+                remainder = rest;
+
+                return Some(chunk);
+            }
+        );
+    }
+
+    fn is_number(chunk: &str) -> bool {
+        return chunk.starts_with(|character: char| { return character.is_ascii_digit(); });
+    }
+
+    fn compare_digits(left: &str, right: &str) -> Ordering {
+        let left = left.trim_start_matches('0');
+        let right = right.trim_start_matches('0');
+
+        return left.len().cmp(&right.len()).then_with(|| { return left.cmp(right); });
+    }
+
+    fn compare_numeric(left: &str, right: &str) -> Ordering {
+        match (Self::parse_number(left), Self::parse_number(right)) {
+            (Some(left_number), Some(right_number)) => {
+                return left_number.total_cmp(&right_number).then_with(|| { return left.cmp(right); });
+            },
+            (Some(_), None) => {
+                return Ordering::Greater;
+            },
+            (None, Some(_)) => {
+                return Ordering::Less;
+            },
+            (None, None) => {
+                return left.cmp(right);
+            },
+        }
+    }
+
+    fn parse_number(line: &str) -> Option<f64> {
+        let line = line.trim_start();
+        let mut length = 0;
+
+        for (index, character) in line.char_indices() {
+            let is_number_part = character.is_ascii_digit()
+                || (index == 0 && matches!(character, '-' | '+'))
+                || (character == '.' && !line[..index].contains('.'));
+
+            if !is_number_part {
+                break;
+            }
+
+            length = index.saturating_add(1);
+        }
+
+        return line[..length].parse::<f64>().ok();
+    }
+
+    fn get_column(line: &str, column: usize, delimiter: Option<char>) -> &str {
+        let index = column.saturating_sub(1);
+
+        if let Some(delimiter) = delimiter {
+            return line.split(delimiter).nth(index).map_or("", str::trim);
+        } else {
+            return line.split_whitespace().nth(index).unwrap_or("");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply(operation: LineOperation, lines: &[&str]) -> Vec<String> {
+        let mut lines = lines.iter().map(|line| { return String::from(*line); }).collect();
+
+        LineSorter::apply(operation, &mut lines);
+
+        return lines;
+    }
+
+    #[test]
+    fn sorts_lexically_and_case_insensitively() {
+        assert_eq!(apply(LineOperation::SortLexical, &["b", "B", "a"]), ["B", "a", "b"]);
+        assert_eq!(apply(LineOperation::SortCaseInsensitive, &["b", "C", "a"]), ["a", "b", "C"]);
+    }
+
+    #[test]
+    fn sorts_digit_runs_naturally() {
+        assert_eq!(apply(LineOperation::SortNatural, &["file10", "file2", "file1"]), ["file1", "file2", "file10"]);
+        assert_eq!(apply(LineOperation::SortNatural, &["a02", "a1", "a2"]), ["a1", "a02", "a2"]);
+    }
+
+    #[test]
+    fn sorts_numbers_after_other_lines() {
+        assert_eq!(apply(LineOperation::SortNumeric, &["10", "9", "x", "-1.5", "2.25"]), ["x", "-1.5", "2.25", "9", "10"]);
+    }
+
+    #[test]
+    fn sorts_by_column() {
+        assert_eq!(apply(LineOperation::SortByColumn(2, None), &["b 2", "a 10", "c 1"]), ["c 1", "b 2", "a 10"]);
+        assert_eq!(apply(LineOperation::SortByColumn(2, Some(',')), &["b, z", "a, y", "c"]), ["c", "a, y", "b, z"]);
+    }
+
+    #[test]
+    fn reverses_deduplicates_and_shuffles() {
+        assert_eq!(apply(LineOperation::Reverse, &["a", "b", "c"]), ["c", "b", "a"]);
+        assert_eq!(apply(LineOperation::Unique, &["b", "a", "b", "a"]), ["b", "a"]);
+
+        let mut shuffled = apply(LineOperation::Shuffle, &["a", "b", "c", "d"]);
+
+        shuffled.sort();
+        assert_eq!(shuffled, ["a", "b", "c", "d"]);
+    }
+}
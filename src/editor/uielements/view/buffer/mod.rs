@@ -0,0 +1,1209 @@
+mod change;
+mod changekind;
+mod history;
+mod transaction;
+mod killring;
+mod registers;
+mod linesorter;
+mod atomicfile;
+
+use std::{
+    io::{
+        Error,
+        ErrorKind,
+    },
+    collections::HashMap,
+    fs::read,
+    cmp::{
+        min,
+        max,
+    },
+    ops::RangeInclusive,
+};
+use unicode_segmentation::UnicodeSegmentation;
+use super::{
+    Line,
+    Location,
+    FileInfo,
+    LineOperation,
+    LineEnding,
+    Encoding,
+    RawByte,
+};
+pub use change::Change;
+use changekind::ChangeKind;
+use history::History;
+use transaction::Transaction;
+use killring::KillRing;
+use linesorter::LineSorter;
+pub use registers::Registers;
+use atomicfile::AtomicFile;
+
+const BRACKET_PAIRS: [(&str, &str); 3] = [
+    ("(", ")"),
+    ("[", "]"),
+    ("{", "}"),
+];
+const MAX_BRACKET_SEARCH_LINES: usize = 10_000;
+
+#[derive(Default)]
+pub struct Buffer {
+    pub lines: Vec<Line>,
+    pub modified: bool,
+    pub file_info: FileInfo,
+    pub line_ending: LineEnding,
+    pub missing_final_newline: bool,
+    pub has_normalized_line_endings: bool,
+    pub encoding: Encoding,
+    raw_bytes_encoding: Encoding,
+    history: History,
+    revision: usize,
+}
+
+impl Buffer {
+    pub fn load(file: &str, fallback_encoding: Encoding) -> Result<Self, Error> {
+        let bytes = read(file)?;
+
+        return Ok(Self::from_bytes(file, &bytes, Encoding::detect(&bytes, fallback_encoding)));
+    }
+
+    pub fn load_as(file: &str, encoding: Encoding) -> Result<Self, Error> {
+        return Ok(Self::from_bytes(file, &read(file)?, encoding));
+    }
+
+    fn from_bytes(file: &str, bytes: &[u8], encoding: Encoding) -> Self {
+        let (data, raw_bytes) = encoding.decode(bytes);
+        let line_ending = LineEnding::detect(&data);
+        let terminated_lines_count = data.matches('\n').count();
+        let mut lines = Vec::new();
+
+        if !data.is_empty() {
+            let segments = Self::split_segments(data.strip_suffix('\n').unwrap_or(&data), &raw_bytes);
+
+            for (line_index, (line_data, line_raw_bytes)) in segments.into_iter().enumerate() {
+                if line_ending == LineEnding::Crlf && line_index < terminated_lines_count {
+                    lines.push(Line::with_raw_bytes(line_data.strip_suffix('\r').unwrap_or(line_data), line_raw_bytes));
+                } else {
+                    lines.push(Line::with_raw_bytes(line_data, line_raw_bytes));
+                }
+            }
+        }
+
+        let mut buffer = Self {
+            lines: lines,
+            modified: false,
+            file_info: FileInfo::from(file),
+            line_ending,
+            missing_final_newline: !data.is_empty() && !data.ends_with('\n'),
+            has_normalized_line_endings: line_ending == LineEnding::Crlf && data.matches("\r\n").count() < terminated_lines_count,
+            encoding,
+            raw_bytes_encoding: encoding,
+            history: History::default(),
+            revision: 0,
+        };
+
+        if buffer.has_normalized_line_endings {
+            buffer.mark_format_changed();
+        }
+
+        return buffer;
+    }
+
+    fn save_file(&self, file_info: &FileInfo) -> Result<(), Error> {
+        if let Some(file_path) = &file_info.get_path() {
+            AtomicFile::write(file_path, &self.encode_contents()?)?;
+        }
+
+        return Ok(());
+    }
+
+    fn encode_contents(&self) -> Result<Vec<u8>, Error> {
+        let (contents, raw_bytes) = self.get_contents();
+
+        if !raw_bytes.is_empty() && !self.encoding.can_keep_raw_bytes_from(self.raw_bytes_encoding) {
+            return Err(
+                Error::new(
+                    ErrorKind::InvalidData,
+                    format!("bytes that could not be read as {} cannot be saved as {}", self.raw_bytes_encoding, self.encoding),
+                )
+            );
+        }
+
+        return self.encoding.encode(&contents, &raw_bytes);
+    }
+
+    fn get_contents(&self) -> (String, Vec<RawByte>) {
+        let line_ending = self.line_ending.as_str();
+        let mut contents = String::new();
+        let mut raw_bytes = Vec::new();
+
+        for (line_index, line) in self.lines.iter().enumerate() {
+            if line_index > 0 {
+                contents.push_str(line_ending);
+            }
+
+            let offset = contents.len();
+
+            raw_bytes.extend(
+                line.get_raw_bytes(0..line.grapheme_count())
+                    .into_iter()
+                    .map(
+                        |raw_byte| {
+                            return raw_byte.shifted_forward(offset);
+                        }
+                    )
+            );
+            contents.push_str(line);
+        }
+
+        if !self.is_empty() && !self.missing_final_newline {
+            contents.push_str(line_ending);
+        }
+
+        return (contents, raw_bytes);
+    }
+
+    fn split_segments<'a>(text: &'a str, raw_bytes: &[RawByte]) -> Vec<(&'a str, Vec<RawByte>)> {
+        let mut segment_start: usize = 0;
+
+        return text
+            .split('\n')
+            .map(
+                |segment| {
+                    let segment_end = segment_start.saturating_add(segment.len());
+                    let segment_raw_bytes = RawByte::get_in_range(raw_bytes, segment_start..segment_end);
+
+                    segment_start = segment_end.saturating_add(1);
+
+                    return (segment, segment_raw_bytes);
+                }
+            )
+            .collect();
+    }
+
+    pub fn set_line_ending(&mut self, line_ending: LineEnding) -> bool {
+        if self.line_ending == line_ending {
+            return false;
+        }
+
+        self.line_ending = line_ending;
+        self.mark_format_changed();
+
+        return true;
+    }
+
+    pub fn set_encoding(&mut self, encoding: Encoding) -> bool {
+        if self.encoding == encoding {
+            return false;
+        }
+
+        self.encoding = encoding;
+        self.mark_format_changed();
+
+        return true;
+    }
+
+    pub fn toggle_final_newline(&mut self) -> bool {
+        self.missing_final_newline = !self.missing_final_newline;
+        self.mark_format_changed();
+
+        return !self.missing_final_newline;
+    }
+
+    fn mark_format_changed(&mut self) {
+        self.modified = true;
+        self.history.forget_save_point();
+    }
+
+    pub fn save_as(&mut self, file_name: &str) -> Result<(), Error> {
+        let file_info = FileInfo::from(file_name);
+
+        self.save_file(&file_info)?;
+        self.file_info = file_info;
+        self.modified = false;
+        self.history.mark_saved();
+
+        return Ok(());
+    }
+
+    pub fn save(&mut self) -> Result<(), Error> {
+        self.save_file(&self.file_info)?;
+        self.modified = false;
+        self.history.mark_saved();
+
+        return Ok(());
+    }
+
+    pub fn insert_char(&mut self, character: char, at_where: Location) {
+        self.insert_text(&String::from(character), at_where);
+    }
+
+    pub fn insert_line(&mut self, at_where: Location) {
+        let at_where = if at_where.line_index >= self.height() {
+            self.end_location()
+        } else {
+            at_where
+        };
+
+        self.insert_text("\n", at_where);
+    }
+
+    pub fn remove_char(&mut self, at_where: Location) {
+        if let Some(line) = self.lines.get(at_where.line_index) {
+            let grapheme_count = line.grapheme_count();
+
+            if at_where.grapheme_index >= grapheme_count && self.height() > at_where.line_index.saturating_add(1) {
+                self.remove_text(
+                    Location {
+                        grapheme_index: grapheme_count,
+                        line_index: at_where.line_index,
+                    },
+                    Location {
+                        grapheme_index: 0,
+                        line_index: at_where.line_index.saturating_add(1),
+                    },
+                );
+            } else if at_where.grapheme_index < grapheme_count {
+                self.remove_text(
+                    at_where,
+                    Location {
+                        grapheme_index: at_where.grapheme_index.saturating_add(1),
+                        line_index: at_where.line_index,
+                    },
+                );
+            }
+        }
+    }
+
+    pub fn insert_text(&mut self, text: &str, at_where: Location) -> Location {
+        return self.insert_text_with_raw_bytes(text, &[], at_where);
+    }
+
+    fn insert_text_with_raw_bytes(&mut self, text: &str, raw_bytes: &[RawByte], at_where: Location) -> Location {
+        let change = self.apply_insert(text, raw_bytes, at_where);
+        let end = change.end;
+
+        self.history.record(change);
+        self.modified = true;
+
+        return end;
+    }
+
+    pub fn remove_text(&mut self, start: Location, end: Location) -> String {
+        let change = self.apply_remove(start, end);
+        let text = change.text.clone();
+
+        if !text.is_empty() {
+            self.history.record(change);
+            self.modified = true;
+        }
+
+        return text;
+    }
+
+    pub fn get_text(&self, start: Location, end: Location) -> String {
+        return self.get_text_with_raw_bytes(start, end).0;
+    }
+
+    fn get_text_with_raw_bytes(&self, start: Location, end: Location) -> (String, Vec<RawByte>) {
+        let mut result = String::new();
+        let mut raw_bytes = Vec::new();
+
+        for line_index in start.line_index..=min(end.line_index, self.height().saturating_sub(1)) {
+            let Some(line) = self.lines.get(line_index) else {
+                break;
+            };
+
+            let from = if line_index == start.line_index {
+                start.grapheme_index
+            } else {
+                0
+            };
+            let to = if line_index == end.line_index {
+                end.grapheme_index
+            } else {
+                line.grapheme_count()
+            };
+
+            if line_index != start.line_index {
+                result.push('\n');
+            }
+
+            let offset = result.len();
+
+            raw_bytes.extend(
+                line.get_raw_bytes(from..to)
+                    .into_iter()
+                    .map(
+                        |raw_byte| {
+                            return raw_byte.shifted_forward(offset);
+                        }
+                    )
+            );
+            result.push_str(line.substr(from..to));
+        }
+
+        return (result, raw_bytes);
+    }
+
+    pub fn get_lines_range(&self, line_indices: RangeInclusive<usize>) -> (Location, Location) {
+        let first_line_index = *line_indices.start();
+        let last_line_index = min(*line_indices.end(), self.height().saturating_sub(1));
+        let line_length = |line_index: usize| {
+            return self.lines.get(line_index).map_or(0, Line::grapheme_count);
+        };
+
+        if last_line_index.saturating_add(1) < self.height() {
+            return (
+                Location {
+                    line_index: first_line_index,
+                    grapheme_index: 0,
+                },
+                Location {
+                    line_index: last_line_index.saturating_add(1),
+                    grapheme_index: 0,
+                },
+            );
+        } else if first_line_index > 0 {
+            return (
+                Location {
+                    line_index: first_line_index.saturating_sub(1),
+                    grapheme_index: line_length(first_line_index.saturating_sub(1)),
+                },
+                Location {
+                    line_index: last_line_index,
+                    grapheme_index: line_length(last_line_index),
+                },
+            );
+        } else {
+            return (
+                Location::default(),
+                Location {
+                    line_index: last_line_index,
+                    grapheme_index: line_length(last_line_index),
+                },
+            );
+        }
+    }
+
+    pub fn duplicate_lines(&mut self, line_indices: RangeInclusive<usize>) -> usize {
+        if self.is_empty() {
+            return 0;
+        }
+
+        let first_line_index = *line_indices.start();
+        let last_line_index = min(*line_indices.end(), self.height().saturating_sub(1));
+        let end_of_block = Location {
+            line_index: last_line_index,
+            grapheme_index: self.lines[last_line_index].grapheme_count(),
+        };
+        let (text, raw_bytes) = self.get_text_with_raw_bytes(
+            Location {
+                line_index: first_line_index,
+                grapheme_index: 0,
+            },
+            end_of_block,
+        );
+
+        self.insert_text_with_raw_bytes(&format!("\n{text}"), &Self::shift_raw_bytes(&raw_bytes, 1), end_of_block);
+
+        return last_line_index.saturating_sub(first_line_index).saturating_add(1);
+    }
+
+    pub fn delete_lines(&mut self, line_indices: RangeInclusive<usize>) -> String {
+        if self.is_empty() || *line_indices.start() >= self.height() {
+            return String::new();
+        }
+
+        let (start, end) = self.get_lines_range(line_indices);
+
+        return self.remove_text(start, end);
+    }
+
+    pub fn move_lines_up(&mut self, line_indices: RangeInclusive<usize>) -> bool {
+        let first_line_index = *line_indices.start();
+        let last_line_index = min(*line_indices.end(), self.height().saturating_sub(1));
+
+        if first_line_index == 0 || first_line_index > last_line_index {
+            return false;
+        }
+
+        let line_above = &self.lines[first_line_index.saturating_sub(1)];
+        let line_above_raw_bytes = line_above.get_raw_bytes(0..line_above.grapheme_count());
+        let line_above = line_above.to_string();
+
+        self.remove_text(
+            Location {
+                line_index: first_line_index.saturating_sub(1),
+                grapheme_index: 0,
+            },
+            Location {
+                line_index: first_line_index,
+                grapheme_index: 0,
+            },
+        );
+
+        let end_of_block = Location {
+            line_index: last_line_index.saturating_sub(1),
+            grapheme_index: self.lines[last_line_index.saturating_sub(1)].grapheme_count(),
+        };
+
+        self.insert_text_with_raw_bytes(&format!("\n{line_above}"), &Self::shift_raw_bytes(&line_above_raw_bytes, 1), end_of_block);
+
+        return true;
+    }
+
+    pub fn move_lines_down(&mut self, line_indices: RangeInclusive<usize>) -> bool {
+        let first_line_index = *line_indices.start();
+        let last_line_index = *line_indices.end();
+
+        if last_line_index.saturating_add(1) >= self.height() || first_line_index > last_line_index {
+            return false;
+        }
+
+        let line_below = &self.lines[last_line_index.saturating_add(1)];
+        let line_below_raw_bytes = line_below.get_raw_bytes(0..line_below.grapheme_count());
+        let line_below = line_below.to_string();
+
+        self.remove_text(
+            Location {
+                line_index: last_line_index,
+                grapheme_index: self.lines[last_line_index].grapheme_count(),
+            },
+            Location {
+                line_index: last_line_index.saturating_add(1),
+                grapheme_index: self.lines[last_line_index.saturating_add(1)].grapheme_count(),
+            },
+        );
+        self.insert_text_with_raw_bytes(
+            &format!("{line_below}\n"),
+            &line_below_raw_bytes,
+            Location {
+                line_index: first_line_index,
+                grapheme_index: 0,
+            },
+        );
+
+        return true;
+    }
+
+    pub fn apply_line_operation(&mut self, line_indices: RangeInclusive<usize>, operation: LineOperation) -> usize {
+        if self.is_empty() || *line_indices.start() >= self.height() {
+            return 0;
+        }
+
+        let first_line_index = *line_indices.start();
+        let last_line_index = min(*line_indices.end(), self.height().saturating_sub(1));
+        let original_lines = self.get_lines(first_line_index..=last_line_index);
+        let mut lines = original_lines.clone();
+
+        LineSorter::apply(operation, &mut lines);
+
+        if lines == original_lines {
+            return 0;
+        }
+
+        let mut raw_bytes_by_line: HashMap<&str, Vec<Vec<RawByte>>> = HashMap::new();
+
+        for line in self.lines[first_line_index..=last_line_index].iter().rev() {
+            raw_bytes_by_line
+                .entry(line)
+                .or_default()
+                .push(line.get_raw_bytes(0..line.grapheme_count()));
+        }
+
+        let lines_raw_bytes = lines
+            .iter()
+            .map(
+                |line| {
+                    return raw_bytes_by_line
+                        .get_mut(line.as_str())
+                        .and_then(Vec::pop)
+                        .unwrap_or_default();
+                }
+            )
+            .collect::<Vec<Vec<RawByte>>>();
+
+        self.replace_lines_with_raw_bytes(first_line_index..=last_line_index, &lines, &lines_raw_bytes);
+
+        return lines.len();
+    }
+
+    pub fn get_lines(&self, line_indices: RangeInclusive<usize>) -> Vec<String> {
+        let last_line_index = min(*line_indices.end(), self.height().saturating_sub(1));
+
+        return self
+            .lines
+            .get(*line_indices.start()..=last_line_index)
+            .unwrap_or_default()
+            .iter()
+            .map(Line::to_string)
+            .collect();
+    }
+
+    pub fn replace_lines(&mut self, line_indices: RangeInclusive<usize>, lines: &[String]) {
+        self.replace_lines_with_raw_bytes(line_indices, lines, &[]);
+    }
+
+    fn replace_lines_with_raw_bytes(&mut self, line_indices: RangeInclusive<usize>, lines: &[String], lines_raw_bytes: &[Vec<RawByte>]) {
+        let first_line_index = *line_indices.start();
+        let last_line_index = min(*line_indices.end(), self.height().saturating_sub(1));
+
+        if first_line_index > last_line_index {
+            return;
+        }
+
+        let start = Location {
+            line_index: first_line_index,
+            grapheme_index: 0,
+        };
+
+        self.remove_text(
+            start,
+            Location {
+                line_index: last_line_index,
+                grapheme_index: self.lines[last_line_index].grapheme_count(),
+            },
+        );
+        let mut raw_bytes = Vec::new();
+        let mut offset: usize = 0;
+
+        for (line, line_raw_bytes) in lines.iter().zip(lines_raw_bytes) {
+            raw_bytes.extend(Self::shift_raw_bytes(line_raw_bytes, offset));
+            offset = offset.saturating_add(line.len()).saturating_add(1);
+        }
+
+        self.insert_text_with_raw_bytes(&lines.join("\n"), &raw_bytes, start);
+    }
+
+    fn shift_raw_bytes(raw_bytes: &[RawByte], by: usize) -> Vec<RawByte> {
+        return raw_bytes
+            .iter()
+            .map(
+                |raw_byte| {
+                    return raw_byte.shifted_forward(by);
+                }
+            )
+            .collect();
+    }
+
+    pub fn join_lines(&mut self, line_indices: RangeInclusive<usize>) -> Option<Location> {
+        let first_line_index = *line_indices.start();
+        let joins_count = max(line_indices.end().saturating_sub(first_line_index), 1);
+        let mut first_join = None;
+
+        for _ in 0..joins_count {
+            if first_line_index.saturating_add(1) >= self.height() {
+                break;
+            }
+
+            let line = &self.lines[first_line_index];
+            let next_line = &self.lines[first_line_index.saturating_add(1)];
+            let start = Location {
+                line_index: first_line_index,
+                grapheme_index: line.get_trailing_whitespace_start(),
+            };
+            let end = Location {
+                line_index: first_line_index.saturating_add(1),
+                grapheme_index: next_line.get_leading_whitespace_count(),
+            };
+            let separator = if start.grapheme_index == 0 || end.grapheme_index == next_line.grapheme_count() {
+                ""
+            } else {
+                " "
+            };
+
+            self.remove_text(start, end);
+
+            if !separator.is_empty() {
+                self.insert_text(separator, start);
+            }
+
+            first_join.get_or_insert(start);
+        }
+
+        return first_join;
+    }
+
+    pub fn get_pending_changes(&self) -> &[Change] {
+        return self.history.get_pending();
+    }
+
+    pub fn find_all(&self, query: &str) -> Vec<(Location, Location)> {
+        let query_length = Line::from(query).grapheme_count();
+        let mut matches = Vec::new();
+
+        if query.is_empty() || query.contains('\n') {
+            return matches;
+        }
+
+        for (line_index, line) in self.lines.iter().enumerate() {
+            let mut from_grapheme_index = 0;
+
+            while let Some(grapheme_index) = line.search_next(query, from_grapheme_index) {
+                let end_grapheme_index = grapheme_index.saturating_add(query_length);
+
+                matches.push(
+                    (
+                        Location {
+                            line_index,
+                            grapheme_index,
+                        },
+                        Location {
+                            line_index,
+                            grapheme_index: end_grapheme_index,
+                        },
+                    )
+                );
+
+                from_grapheme_index = max(end_grapheme_index, grapheme_index.saturating_add(1));
+            }
+        }
+
+        return matches;
+    }
+
+    pub fn commit_edit(&mut self, location_before: Location, location_after: Location) {
+        self.history.commit(location_before, location_after);
+        self.modified = !self.history.is_at_save_point();
+    }
+
+    pub fn undo(&mut self) -> Option<(Location, Vec<Change>)> {
+        let (changes, location) = self
+            .history
+            .take_undo()
+            .map(
+                |transaction| {
+                    return (transaction.changes.clone(), transaction.location_before);
+                }
+            )?;
+
+        let applied_changes = changes
+            .iter()
+            .rev()
+            .map(
+                |change| {
+                    match change.kind {
+                        ChangeKind::Insert => {
+                            return self.apply_remove(change.start, change.end);
+                        },
+                        ChangeKind::Remove => {
+                            return self.apply_insert(&change.text, &change.raw_bytes, change.start);
+                        },
+                    }
+                }
+            )
+            .collect();
+
+        self.modified = !self.history.is_at_save_point();
+
+        return Some((location, applied_changes));
+    }
+
+    pub fn redo(&mut self) -> Option<(Location, Vec<Change>)> {
+        let (changes, location) = self
+            .history
+            .take_redo()
+            .map(
+                |transaction| {
+                    return (transaction.changes.clone(), transaction.location_after);
+                }
+            )?;
+
+        let applied_changes = changes
+            .iter()
+            .map(
+                |change| {
+                    match change.kind {
+                        ChangeKind::Insert => {
+                            return self.apply_insert(&change.text, &change.raw_bytes, change.start);
+                        },
+                        ChangeKind::Remove => {
+                            return self.apply_remove(change.start, change.end);
+                        },
+                    }
+                }
+            )
+            .collect();
+
+        self.modified = !self.history.is_at_save_point();
+
+        return Some((location, applied_changes));
+    }
+
+    fn apply_insert(&mut self, text: &str, raw_bytes: &[RawByte], at_where: Location) -> Change {
+        let mut start = at_where;
+        let mut text = String::from(text);
+        let mut raw_bytes = raw_bytes.to_vec();
+
+        self.revision = self.revision.wrapping_add(1);
+
+        if start.line_index >= self.height() {
+            if let Some(last_line) = self.lines.last() {
+                start = Location {
+                    grapheme_index: last_line.grapheme_count(),
+                    line_index: self.height().saturating_sub(1),
+                };
+                text.insert(0, '\n');
+                raw_bytes = Self::shift_raw_bytes(&raw_bytes, 1);
+            } else {
+                self.lines.push(Line::default());
+
+                start = Location::default();
+            }
+        }
+
+        let line = &mut self.lines[start.line_index];
+
+        start.grapheme_index = min(start.grapheme_index, line.grapheme_count());
+
+        let remainder = line.split(start.grapheme_index);
+        let mut segments = Self::split_segments(&text, &raw_bytes).into_iter();
+
+        if let Some((segment, segment_raw_bytes)) = segments.next() {
+            line.append(&Line::with_raw_bytes(segment, segment_raw_bytes));
+        }
+
+        let mut line_index = start.line_index;
+
+        for (segment, segment_raw_bytes) in segments {
+            line_index = line_index.saturating_add(1);
+
+            self.lines.insert(line_index, Line::with_raw_bytes(segment, segment_raw_bytes));
+        }
+
+        let last_line = &mut self.lines[line_index];
+        let end = Location {
+            grapheme_index: last_line.grapheme_count(),
+            line_index,
+        };
+
+        last_line.append(&remainder);
+
+        return Change {
+            kind: ChangeKind::Insert,
+            start,
+            end,
+            text,
+            raw_bytes,
+        };
+    }
+
+    fn apply_remove(&mut self, start: Location, end: Location) -> Change {
+        let end = if end.line_index >= self.height() {
+            self.end_location()
+        } else {
+            end
+        };
+        let (text, raw_bytes) = self.get_text_with_raw_bytes(start, end);
+
+        self.revision = self.revision.wrapping_add(1);
+
+        if text.is_empty() {
+            return Change {
+                kind: ChangeKind::Remove,
+                start,
+                end: start,
+                text,
+                raw_bytes,
+            };
+        }
+
+        let remainder = self.lines[end.line_index].split(end.grapheme_index);
+        let line = &mut self.lines[start.line_index];
+
+        line.split(start.grapheme_index);
+        line.append(&remainder);
+
+        self.lines.drain(start.line_index.saturating_add(1)..=end.line_index);
+
+        return Change {
+            kind: ChangeKind::Remove,
+            start,
+            end,
+            text,
+            raw_bytes,
+        };
+    }
+
+    pub fn search_next(&self, query: &str, from: Location) -> Option<Location> {
+        if query.is_empty() {
+            return None;
+        }
+
+        let mut is_first = true;
+
+        for (line_index, line) in self
+            .lines
+            .iter()
+            .enumerate()
+            .cycle()
+            .skip(
+                from.line_index
+            )
+            .take(
+                self
+                    .lines
+                    .len()
+                    .saturating_add(1)
+                ) {
+            let from_grapheme_index = if is_first {
+                is_first = false;
+                from.grapheme_index
+            } else {
+                0
+            };
+
+            if let Some(grapheme_index) = line.search_next(query, from_grapheme_index) {
+                return Some(
+                    Location {
+                        grapheme_index,
+                        line_index,
+                    }
+                );
+            }
+        }
+
+        return None;
+    }
+
+    pub fn search_previous(&self, query: &str, from: Location) -> Option<Location> {
+        if query.is_empty() {
+            return None;
+        }
+
+        let mut is_first = true;
+
+        for (line_index, line) in self
+            .lines
+            .iter()
+            .enumerate()
+            .rev()
+            .cycle()
+            .skip(
+                self
+                    .lines
+                    .len()
+                    .saturating_sub(from.line_index)
+                    .saturating_sub(1)
+            )
+            .take(
+                self
+                    .lines
+                    .len()
+                    .saturating_add(1)
+            ) {
+            let from_grapheme_index = if is_first {
+                is_first = false;
+                from.grapheme_index
+            } else {
+                line.grapheme_count()
+            };
+
+            if let Some(grapheme_index) = line.search_previous(query, from_grapheme_index) {
+                return Some(
+                    Location {
+                        grapheme_index,
+                        line_index,
+                    }
+                );
+            }
+        }
+
+        return None;
+    }
+
+    pub fn get_next_word_location(&self, from: Location, subword: bool) -> Location {
+        let Some(line) = self.lines.get(from.line_index) else {
+            return from;
+        };
+
+        if from.grapheme_index < line.grapheme_count() {
+            return Location {
+                line_index: from.line_index,
+                grapheme_index: line.next_word_end(from.grapheme_index, subword),
+            };
+        }
+
+        return self.lines
+            .get(from.line_index.saturating_add(1))
+            .map_or(
+                from,
+                |next_line| {
+                    return Location {
+                        line_index: from.line_index.saturating_add(1),
+                        grapheme_index: next_line.next_word_end(0, subword),
+                    };
+                }
+            );
+    }
+
+    pub fn get_previous_word_location(&self, from: Location, subword: bool) -> Location {
+        if from.grapheme_index > 0 {
+            return Location {
+                line_index: from.line_index,
+                grapheme_index: self.lines
+                    .get(from.line_index)
+                    .map_or(
+                        0,
+                        |line| {
+                            return line.previous_word_start(from.grapheme_index, subword);
+                        }
+                    ),
+            };
+        }
+
+        if from.line_index == 0 || self.is_empty() {
+            return from;
+        }
+
+        let line_index = min(from.line_index.saturating_sub(1), self.height().saturating_sub(1));
+
+        return Location {
+            line_index,
+            grapheme_index: self.lines
+                .get(line_index)
+                .map_or(
+                    0,
+                    |line| {
+                        return line.previous_word_start(line.grapheme_count(), subword);
+                    }
+                ),
+        };
+    }
+
+    pub fn find_matching_bracket(&self, at_where: Location) -> Option<Location> {
+        let bracket = self.lines.get(at_where.line_index)?.graphemes(true).nth(at_where.grapheme_index)?;
+        let (opener, closer) = BRACKET_PAIRS
+            .into_iter()
+            .find(
+                |(opener, closer)| {
+                    return *opener == bracket || *closer == bracket;
+                }
+            )?;
+        let mut depth: usize = 0;
+
+        if bracket == opener {
+            for (line_index, line) in self.lines.iter().enumerate().skip(at_where.line_index).take(MAX_BRACKET_SEARCH_LINES) {
+                let skipped_count = if line_index == at_where.line_index {
+                    at_where.grapheme_index
+                } else {
+                    0
+                };
+
+                for (grapheme_index, grapheme) in line.graphemes(true).enumerate().skip(skipped_count) {
+                    if grapheme == opener {
+                        depth = depth.saturating_add(1);
+                    } else if grapheme == closer {
+                        depth = depth.saturating_sub(1);
+
+                        if depth == 0 {
+                            return Some(
+                                Location {
+                                    line_index,
+                                    grapheme_index,
+                                }
+                            );
+                        }
+                    }
+                }
+            }
+        } else {
+            let first_line_index = at_where.line_index.saturating_sub(MAX_BRACKET_SEARCH_LINES);
+
+            for line_index in (first_line_index..=at_where.line_index).rev() {
+                let graphemes: Vec<&str> = self.lines.get(line_index)?.graphemes(true).collect();
+                let end = if line_index == at_where.line_index {
+                    at_where.grapheme_index.saturating_add(1)
+                } else {
+                    graphemes.len()
+                };
+
+                for grapheme_index in (0..end).rev() {
+                    if graphemes[grapheme_index] == closer {
+                        depth = depth.saturating_add(1);
+                    } else if graphemes[grapheme_index] == opener {
+                        depth = depth.saturating_sub(1);
+
+                        if depth == 0 {
+                            return Some(
+                                Location {
+                                    line_index,
+                                    grapheme_index,
+                                }
+                            );
+                        }
+                    }
+                }
+            }
+        }
+
+        return None;
+    }
+
+    pub fn get_next_paragraph_location(&self, from: Location) -> Location {
+        let mut line_index = from.line_index.saturating_add(1);
+
+        while line_index < self.height() && self.is_blank_line(line_index) {
+            line_index = line_index.saturating_add(1);
+        }
+
+        while line_index < self.height() && !self.is_blank_line(line_index) {
+            line_index = line_index.saturating_add(1);
+        }
+
+        if line_index >= self.height() {
+            return self.end_location();
+        }
+
+        return Location {
+            line_index,
+            grapheme_index: 0,
+        };
+    }
+
+    pub fn get_previous_paragraph_location(&self, from: Location) -> Location {
+        let mut line_index = min(from.line_index, self.height()).saturating_sub(1);
+
+        while line_index > 0 && self.is_blank_line(line_index) {
+            line_index = line_index.saturating_sub(1);
+        }
+
+        while line_index > 0 && !self.is_blank_line(line_index) {
+            line_index = line_index.saturating_sub(1);
+        }
+
+        return Location {
+            line_index,
+            grapheme_index: 0,
+        };
+    }
+
+    fn is_blank_line(&self, line_index: usize) -> bool {
+        return self.lines
+            .get(line_index)
+            .is_none_or(
+                |line| {
+                    return line.trim().is_empty();
+                }
+            );
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.lines.is_empty();
+    }
+
+    pub const fn is_file_loaded(&self) -> bool {
+        return self.file_info.has_path();
+    }
+
+    pub fn end_location(&self) -> Location {
+        return Location {
+            grapheme_index: self.lines.last().map_or(0, Line::grapheme_count),
+            line_index: self.height().saturating_sub(1),
+        };
+    }
+
+    pub const fn get_revision(&self) -> usize {
+        return self.revision;
+    }
+
+    pub fn height(&self) -> usize {
+        return self.lines.len();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_round_trip(bytes: &[u8], encoding: Encoding) {
+        let buffer = Buffer::from_bytes("", bytes, encoding);
+
+        assert!(!buffer.modified);
+        assert_eq!(buffer.encode_contents().unwrap(), bytes);
+    }
+
+    #[test]
+    fn saves_crlf_files_unchanged() {
+        assert_round_trip(b"a\r\nb\r\n", Encoding::Utf8);
+        assert_round_trip(b"a\r\nb", Encoding::Utf8);
+        assert_round_trip(b"a\r\n\r\n", Encoding::Utf8);
+        assert_round_trip(b"\xFF\xFEa\0\r\0\n\0", Encoding::Utf16LeBom);
+    }
+
+    #[test]
+    fn saves_mixed_lf_files_unchanged() {
+        assert_round_trip(b"a\nb\r\nc\n", Encoding::Utf8);
+        assert_round_trip(b"a\rb\n", Encoding::Utf8);
+    }
+
+    #[test]
+    fn saves_files_without_a_final_newline_unchanged() {
+        assert_round_trip(b"a\nb", Encoding::Utf8);
+        assert_round_trip(b"a\r", Encoding::Utf8);
+        assert_round_trip(b"", Encoding::Utf8);
+        assert_round_trip(b"\n", Encoding::Utf8);
+        assert_round_trip(b"caf\xE9\xFF", Encoding::Utf8);
+    }
+
+    #[test]
+    fn normalizes_mixed_crlf_files() {
+        let buffer = Buffer::from_bytes("", b"a\r\nb\r\nc\n", Encoding::Utf8);
+
+        assert!(buffer.modified);
+        assert!(buffer.has_normalized_line_endings);
+        assert_eq!(buffer.line_ending, LineEnding::Crlf);
+        assert_eq!(buffer.get_contents().0, "a\r\nb\r\nc\r\n");
+    }
+
+    #[test]
+    fn keeps_real_characters_next_to_raw_bytes() {
+        let mut bytes = "\u{FFFD}\u{10FFFF}".as_bytes().to_vec();
+
+        bytes.extend_from_slice(b"\xFF\n");
+
+        assert_round_trip(&bytes, Encoding::Utf8);
+    }
+
+    #[test]
+    fn raw_bytes_survive_line_operations_and_undo() {
+        let bytes = b"b\xFF\na\xFE\n";
+        let mut buffer = Buffer::from_bytes("", bytes, Encoding::Utf8);
+
+        buffer.move_lines_down(0..=0);
+        buffer.move_lines_up(1..=1);
+        buffer.duplicate_lines(0..=1);
+        buffer.delete_lines(2..=3);
+        assert_eq!(buffer.encode_contents().unwrap(), bytes);
+
+        buffer.apply_line_operation(0..=1, LineOperation::SortLexical);
+        assert_eq!(buffer.encode_contents().unwrap(), b"a\xFE\nb\xFF\n");
+
+        buffer.commit_edit(Location::default(), Location::default());
+        buffer.delete_lines(0..=1);
+        buffer.commit_edit(Location::default(), Location::default());
+        buffer.undo();
+        assert_eq!(buffer.encode_contents().unwrap(), b"a\xFE\nb\xFF\n");
+    }
+
+    #[test]
+    fn refuses_to_save_raw_bytes_in_another_encoding() {
+        let mut buffer = Buffer::from_bytes("", b"a\xFF\n", Encoding::Utf8);
+
+        buffer.set_encoding(Encoding::Utf16Le);
+        assert!(buffer.encode_contents().is_err_and(|error| { return error.kind() == ErrorKind::InvalidData; }));
+
+        buffer.set_encoding(Encoding::Utf8Bom);
+        assert_eq!(buffer.encode_contents().unwrap(), b"\xEF\xBB\xBFa\xFF\n");
+
+        buffer.remove_char(
+            Location {
+                line_index: 0,
+                grapheme_index: 1,
+            }
+        );
+        buffer.set_encoding(Encoding::Utf16Le);
+        assert_eq!(buffer.encode_contents().unwrap(), b"a\0\n\0");
+    }
+}
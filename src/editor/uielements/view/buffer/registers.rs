@@ -0,0 +1,39 @@
+use std::collections::BTreeMap;
+use crate::editor::ClipboardEntry;
+use super::KillRing;
+
+#[derive(Default)]
+pub struct Registers {
+    pub kill_ring: KillRing,
+    named: BTreeMap<char, ClipboardEntry>,
+}
+
+impl Registers {
+    pub fn kill(&mut self, text: &str, append: bool, backward: bool) -> ClipboardEntry {
+        if append {
+            self.kill_ring.append(text, backward);
+        } else {
+            self.kill_ring.push(ClipboardEntry::from(text));
+        }
+
+        return self.kill_ring.head().cloned().unwrap_or_default();
+    }
+
+    pub fn set(&mut self, name: char, entry: ClipboardEntry) {
+        if Self::is_valid_name(name) {
+            self.named.insert(name.to_ascii_lowercase(), entry);
+        }
+    }
+
+    pub fn get(&self, name: char) -> Option<&ClipboardEntry> {
+        if let Some(index) = name.to_digit(10) {
+            return self.kill_ring.iter().nth(index as usize);
+        }
+
+        return self.named.get(&name.to_ascii_lowercase());
+    }
+
+    pub fn is_valid_name(name: char) -> bool {
+        return name.is_ascii_alphabetic();
+    }
+}
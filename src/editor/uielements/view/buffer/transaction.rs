@@ -0,0 +1,41 @@
+use super::{
+    Change,
+    ChangeKind,
+    Location,
+};
+
+pub struct Transaction {
+    pub changes: Vec<Change>,
+    pub location_before: Location,
+    pub location_after: Location,
+}
+
+impl Transaction {
+    pub fn can_absorb(&self, other: &Self) -> bool {
+        if self.location_after != other.location_before || other.changes.len() != 1 {
+            return false;
+        }
+
+        let (Some(last), Some(next)) = (self.changes.last(), other.changes.first()) else {
+            return false;
+        };
+
+        if last.kind != next.kind || !last.is_single_grapheme() || !next.is_single_grapheme() {
+            return false;
+        }
+
+        match next.kind {
+            ChangeKind::Insert => {
+                return next.start == last.end && (next.is_whitespace() || !last.is_whitespace());
+            },
+            ChangeKind::Remove => {
+                return next.end == last.start || next.start == last.start;
+            },
+        }
+    }
+
+    pub fn absorb(&mut self, other: Self) {
+        self.changes.extend(other.changes);
+        self.location_after = other.location_after;
+    }
+}
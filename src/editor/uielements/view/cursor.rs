@@ -0,0 +1,33 @@
+use super::{
+    Change,
+    Location,
+};
+
+#[derive(Default, Copy, Clone, Eq, PartialEq)]
+pub struct Cursor {
+    pub location: Location,
+    pub anchor: Option<Location>,
+}
+
+impl Cursor {
+    pub fn get_selection_range(&self) -> Option<(Location, Location)> {
+        let anchor = self.anchor?;
+
+        if anchor < self.location {
+            return Some((anchor, self.location));
+        } else if anchor > self.location {
+            return Some((self.location, anchor));
+        } else {
+            return None;
+        }
+    }
+
+    pub fn transform(&mut self, change: &Change) {
+        self.location = change.transform(self.location);
+        self.anchor = self.anchor.map(
+            |anchor| {
+                return change.transform(anchor);
+            }
+        );
+    }
+}
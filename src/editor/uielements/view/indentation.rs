@@ -0,0 +1,142 @@
+use std::collections::HashMap;
+use super::super::super::{
+    Config,
+    Line,
+};
+
+#[derive(Copy, Clone)]
+pub struct Indentation {
+    pub auto_indent: bool,
+    pub hard_tabs: bool,
+    pub width: usize,
+    pub tab_width: usize,
+    pub detect: bool,
+}
+
+impl Default for Indentation {
+    fn default() -> Self {
+        return Self::from(&Config::default());
+    }
+}
+
+impl From<&Config> for Indentation {
+    fn from(config: &Config) -> Self {
+        return Self {
+            auto_indent: config.auto_indent,
+            hard_tabs: config.hard_tabs,
+            width: config.indent_width.max(1),
+            tab_width: config.tab_width.max(1),
+            detect: config.detect_indentation,
+        };
+    }
+}
+
+impl Indentation {
+    pub fn get_unit(&self) -> String {
+        if self.hard_tabs {
+            return String::from("\t");
+        }
+
+        return " ".repeat(self.width);
+    }
+
+    pub fn get_dedent_length(&self, whitespace: &str) -> usize {
+        if whitespace.ends_with('\t') {
+            return 1;
+        }
+
+        let trailing_spaces_count = whitespace
+            .chars()
+            .rev()
+            .take_while(
+                |character| {
+                    return *character == ' ';
+                }
+            )
+            .count();
+
+        if trailing_spaces_count == 0 {
+            return 0;
+        }
+
+        return (trailing_spaces_count.saturating_sub(1) % self.width).saturating_add(1);
+    }
+
+    pub fn get_leading_dedent_length(&self, line: &str) -> usize {
+        if line.starts_with('\t') {
+            return 1;
+        }
+
+        return line
+            .chars()
+            .take(self.width)
+            .take_while(
+                |character| {
+                    return *character == ' ';
+                }
+            )
+            .count();
+    }
+
+    pub fn detect(&mut self, lines: &[Line]) {
+        if !self.detect {
+            return;
+        }
+
+        let mut tab_indented_count: usize = 0;
+        let mut space_indented_count: usize = 0;
+        let mut width_votes: HashMap<usize, usize> = HashMap::new();
+        let mut previous_spaces_count: usize = 0;
+
+        for line in lines {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            if line.starts_with('\t') {
+                tab_indented_count = tab_indented_count.saturating_add(1);
+
+                continue;
+            }
+
+            let spaces_count = line
+                .chars()
+                .take_while(
+                    |character| {
+                        return *character == ' ';
+                    }
+                )
+                .count();
+
+            if spaces_count > 0 {
+                space_indented_count = space_indented_count.saturating_add(1);
+            }
+
+            let delta = spaces_count.abs_diff(previous_spaces_count);
+
+            if (2..=8).contains(&delta) {
+                let votes = width_votes.entry(delta).or_default();
+
+                *votes = votes.saturating_add(1);
+            }
+
+            previous_spaces_count = spaces_count;
+        }
+
+        if tab_indented_count == 0 && space_indented_count == 0 {
+            return;
+        }
+
+        self.hard_tabs = tab_indented_count > space_indented_count;
+
+        if let Some((width, _)) = width_votes
+            .into_iter()
+            .max_by_key(
+                |(width, votes)| {
+                    return (*votes, usize::MAX.saturating_sub(*width));
+                }
+            ) && !self.hard_tabs {
+            self.width = width;
+        }
+    }
+}
@@ -1,5 +1,5 @@
-#[derive(Default, Copy, Clone)]
+#[derive(Default, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
 pub struct Location {
-    pub grapheme_index: usize,
     pub line_index: usize,
+    pub grapheme_index: usize,
 }
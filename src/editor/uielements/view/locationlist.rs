@@ -0,0 +1,70 @@
+use std::cmp::min;
+use super::{
+    Change,
+    Location,
+};
+
+const MAX_LOCATIONS: usize = 100;
+
+#[derive(Default)]
+pub struct LocationList {
+    locations: Vec<Location>,
+    index: usize,
+}
+
+impl LocationList {
+    pub fn push(&mut self, location: Location) {
+        self.locations.retain(
+            |entry| {
+                return entry.line_index != location.line_index;
+            }
+        );
+        self.locations.push(location);
+
+        if self.locations.len() > MAX_LOCATIONS {
+            self.locations.remove(0);
+        }
+
+        self.index = self.locations.len();
+    }
+
+    pub const fn is_at_end(&self) -> bool {
+        return self.index >= self.locations.len();
+    }
+
+    pub fn previous(&mut self, current: Location) -> Option<Location> {
+        let index = self.locations[..min(self.index, self.locations.len())]
+            .iter()
+            .rposition(
+                |location| {
+                    return location.line_index != current.line_index;
+                }
+            )?;
+
+        self.index = index;
+
+        return Some(self.locations[index]);
+    }
+
+    pub fn next(&mut self, current: Location) -> Option<Location> {
+        let start = self.index.saturating_add(1);
+        let offset = self.locations
+            .get(start..)?
+            .iter()
+            .position(
+                |location| {
+                    return location.line_index != current.line_index;
+                }
+            )?;
+
+        self.index = start.saturating_add(offset);
+
+        return Some(self.locations[self.index]);
+    }
+
+    pub fn transform(&mut self, change: &Change) {
+        for location in &mut self.locations {
+            *location = change.transform(*location);
+        }
+    }
+}
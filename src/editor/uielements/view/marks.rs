@@ -0,0 +1,71 @@
+use std::collections::BTreeMap;
+use super::{
+    Change,
+    Location,
+};
+
+#[derive(Default)]
+pub struct Marks {
+    locations: BTreeMap<char, Location>,
+}
+
+impl Marks {
+    pub const fn is_valid_name(name: char) -> bool {
+        return name.is_ascii_alphabetic();
+    }
+
+    pub const fn is_global_name(name: char) -> bool {
+        return name.is_ascii_uppercase();
+    }
+
+    pub fn set(&mut self, name: char, location: Location) {
+        if Self::is_valid_name(name) {
+            self.locations.insert(name, location);
+        }
+    }
+
+    pub fn get(&self, name: char) -> Option<Location> {
+        return self.locations.get(&name).copied();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.locations.is_empty();
+    }
+
+    pub fn get_line_mark(&self, line_index: usize) -> Option<char> {
+        return self.locations
+            .iter()
+            .find(
+                |(_, location)| {
+                    return location.line_index == line_index;
+                }
+            )
+            .map(
+                |(name, _)| {
+                    return *name;
+                }
+            );
+    }
+
+    pub fn get_global(&self) -> Vec<(char, Location)> {
+        return self.locations
+            .iter()
+            .filter(
+                |(name, _)| {
+                    return Self::is_global_name(**name);
+                }
+            )
+            .map(
+                |(name, location)| {
+                    return (*name, *location);
+                }
+            )
+            .collect();
+    }
+
+    pub fn transform(&mut self, change: &Change) {
+        for location in self.locations.values_mut() {
+            *location = change.transform(*location);
+        }
+    }
+}
@@ -3,16 +3,34 @@ mod fileinfo;
 mod location;
 mod searchinfo;
 mod searchdirection;
+mod cursor;
+mod blockselection;
+mod indentation;
+mod transformer;
+mod reflower;
+mod locationlist;
+mod marks;
 
 use std::{
-    cmp::min,
+    cmp::{
+        min,
+        max,
+    },
+    mem::take,
     io::Error,
+    path::Path,
+    ops::{
+        Range,
+        RangeInclusive,
+    },
 };
 use super::{
     super::{
         commands::{
             Edit,
             Move,
+            Transform,
+            LineOperation,
         },
         Terminal,
         Size,
@@ -20,16 +38,41 @@ use super::{
         Row,
         Column,
         FileStatus,
+        LineEnding,
+        Encoding,
+        RawByte,
         VERSION,
         Line,
+        Config,
+        Highlight,
+        AnnotationType,
+        ClipboardEntry,
     },
     UIElement,
 };
-use buffer::Buffer;
+use buffer::{
+    Buffer,
+    Change,
+    Registers,
+};
 use fileinfo::FileInfo;
-use location::Location;
+pub use location::Location;
 use searchinfo::SearchInfo;
 use searchdirection::SearchDirection;
+use cursor::Cursor;
+use blockselection::BlockSelection;
+use indentation::Indentation;
+use transformer::Transformer;
+use locationlist::LocationList;
+use marks::Marks;
+use reflower::{
+    Reflower,
+    DEFAULT_TEXT_WIDTH,
+    COMMIT_MESSAGE_TEXT_WIDTH,
+    PROSE_EXTENSIONS,
+};
+
+const MARK_GUTTER_WIDTH: usize = 2;
 
 #[derive(Default)]
 pub struct View {
@@ -37,8 +80,27 @@ pub struct View {
     needs_redraw: bool,
     size: Size,
     text_location: Location,
+    selection_anchor: Option<Location>,
+    cursors: Vec<Cursor>,
+    block_selection: Option<BlockSelection>,
     scroll_offset: Position,
     search_info: Option<SearchInfo>,
+    registers: Registers,
+    yank_range: Option<(Location, Location)>,
+    subword_mode: bool,
+    indentation: Indentation,
+    auto_pair: bool,
+    text_width: Option<usize>,
+    auto_wrap: bool,
+    fallback_encoding: Encoding,
+    overwrite_mode: bool,
+    transform_error: Option<String>,
+    overwritten: Vec<(Location, String, Option<String>)>,
+    jump_list: LocationList,
+    change_list: LocationList,
+    marks: Marks,
+    bracket_pair: Option<(Location, Location)>,
+    bracket_pair_key: Option<(Location, usize)>,
 }
 
 impl View {
@@ -47,6 +109,10 @@ impl View {
             lines_count: self.buffer.height(),
             current_line_index: self.text_location.line_index,
             modified: self.buffer.modified,
+            overwrite_mode: self.overwrite_mode,
+            line_ending: self.buffer.line_ending,
+            missing_final_newline: self.buffer.missing_final_newline,
+            encoding: self.buffer.encoding,
             file_name: format!(
                 "{}",
                 self.buffer.file_info,
@@ -55,26 +121,635 @@ impl View {
     }
 
     pub fn handle_edit_command(&mut self, command: Edit) {
+        let location_before = self.text_location;
+
+        match command {
+            Edit::LineOperation(_) if self.get_cursors().iter().any(|cursor| { return cursor.get_selection_range().is_none(); }) => {
+                self.collapse_cursors();
+                self.apply_edit_command(command);
+            },
+            Edit::Undo => {
+                self.collapse_cursors();
+                self.clear_selection();
+                self.undo();
+            },
+            Edit::Redo => {
+                self.collapse_cursors();
+                self.clear_selection();
+                self.redo();
+            },
+            _ => {
+                self.for_each_cursor(
+                    |view, _| {
+                        view.apply_edit_command(command);
+                    }
+                );
+            },
+        }
+
+        self.commit_edit(location_before);
+    }
+
+    fn apply_edit_command(&mut self, command: Edit) {
         match command {
             Edit::InsertCharacter(character) => {
-                self.insert_char(character);
+                if !self.wrap_selection(character) {
+                    if !self.delete_selection() && self.overwrite_mode {
+                        self.overwrite_char(character);
+                    } else {
+                        self.type_char(character);
+                    }
+                }
             },
             Edit::InsertTab => {
-                self.insert_tab();
-            }
+                if self.get_selected_lines().count() > 1 {
+                    self.indent_lines();
+                } else {
+                    self.delete_selection();
+                    self.insert_tab();
+                }
+            },
+            Edit::Dedent => {
+                self.dedent_lines();
+            },
+            Edit::Transform(transform) => {
+                self.transform_text(transform);
+            },
+            Edit::LineOperation(operation) => {
+                self.apply_line_operation(operation);
+            },
+            Edit::Increment(delta) => {
+                self.increment_numbers(delta, false);
+            },
+            Edit::IncrementSequence(delta) => {
+                self.increment_numbers(delta, true);
+            },
             Edit::InsertLine => {
+                self.delete_selection();
                 self.insert_line();
             },
             Edit::DeletePrevious => {
-                self.delete_previous();
+                if self.delete_selection() {
+                    return;
+                }
+
+                if self.overwrite_mode {
+                    self.restore_overwritten();
+                } else {
+                    self.delete_previous();
+                }
             },
             Edit::DeleteNext => {
-                self.delete_next();
+                if !self.delete_selection() {
+                    self.delete_next();
+                }
             },
+            Edit::DeleteWordPrevious => {
+                if !self.delete_selection() {
+                    self.delete_word_previous();
+                }
+            },
+            Edit::DeleteWordNext => {
+                if !self.delete_selection() {
+                    self.delete_word_next();
+                }
+            },
+            Edit::DuplicateLine => {
+                self.duplicate_lines();
+            },
+            Edit::DeleteLine => {
+                self.delete_lines();
+            },
+            Edit::MoveLineUp => {
+                self.move_lines_up();
+            },
+            Edit::MoveLineDown => {
+                self.move_lines_down();
+            },
+            Edit::Reflow => {
+                self.reflow();
+            },
+            Edit::JoinLines => {
+                self.join_lines();
+            },
+            Edit::Undo | Edit::Redo => {},
         }
     }
 
     pub fn handle_move_command(&mut self, command: Move) {
+        let location_before = self.text_location;
+
+        self.overwritten.clear();
+        self.for_each_cursor(
+            |view, _| {
+                view.move_cursor(command);
+            }
+        );
+
+        if matches!(command, Move::StartOfFile | Move::EndOfFile | Move::MatchingBracket) {
+            self.record_jump(location_before);
+        }
+    }
+
+    fn move_cursor(&mut self, command: Move) {
+        if let Some((start, end)) = self.get_selection_range() {
+            match command {
+                Move::Left => {
+                    self.clear_selection();
+                    self.text_location = start;
+                    self.scroll_text_location_into_view();
+
+                    return;
+                },
+                Move::Right => {
+                    self.clear_selection();
+                    self.text_location = end;
+                    self.scroll_text_location_into_view();
+
+                    return;
+                },
+                _ => {},
+            }
+        }
+
+        self.clear_selection();
+        self.move_text_location(command);
+    }
+
+    pub fn handle_select_command(&mut self, command: Move) {
+        self.overwritten.clear();
+        self.for_each_cursor(
+            |view, _| {
+                if view.selection_anchor.is_none() {
+                    view.selection_anchor = Some(view.text_location);
+                }
+
+                view.move_text_location(command);
+            }
+        );
+
+        self.set_needs_redraw(true);
+    }
+
+    fn for_each_cursor<F: FnMut(&mut Self, usize)>(&mut self, mut action: F) {
+        if self.cursors.is_empty() {
+            action(self, 0);
+
+            return;
+        }
+
+        let scroll_offset = self.scroll_offset;
+        let mut cursors = take(&mut self.cursors);
+
+        self.block_selection = None;
+
+        cursors.push(self.get_primary_cursor());
+
+        let primary_index = cursors.len().saturating_sub(1);
+        let mut order: Vec<usize> = (0..cursors.len()).collect();
+
+        order.sort_by_key(
+            |index| {
+                return cursors[*index].location;
+            }
+        );
+
+        for (rank, index) in order.into_iter().enumerate().rev() {
+            let pending_changes_count = self.buffer.get_pending_changes().len();
+
+            self.text_location = cursors[index].location;
+            self.selection_anchor = cursors[index].anchor;
+
+            action(self, rank);
+
+            cursors[index] = self.get_primary_cursor();
+
+            for change in self.buffer.get_pending_changes().iter().skip(pending_changes_count) {
+                Self::transform_cursors(&mut cursors, index, change);
+            }
+        }
+
+        let primary = cursors.remove(primary_index);
+
+        cursors.retain(
+            |cursor| {
+                return cursor.location != primary.location;
+            }
+        );
+        cursors.sort_by_key(
+            |cursor| {
+                return cursor.location;
+            }
+        );
+        cursors.dedup_by_key(
+            |cursor| {
+                return cursor.location;
+            }
+        );
+
+        self.text_location = primary.location;
+        self.selection_anchor = primary.anchor;
+        self.cursors = cursors;
+        self.scroll_offset = scroll_offset;
+
+        self.scroll_text_location_into_view();
+        self.set_needs_redraw(true);
+    }
+
+    fn transform_cursors(cursors: &mut [Cursor], skipped_index: usize, change: &Change) {
+        for (index, cursor) in cursors.iter_mut().enumerate() {
+            if index != skipped_index {
+                cursor.transform(change);
+            }
+        }
+    }
+
+    const fn get_primary_cursor(&self) -> Cursor {
+        return Cursor {
+            location: self.text_location,
+            anchor: self.selection_anchor,
+        };
+    }
+
+    fn get_cursors(&self) -> Vec<Cursor> {
+        let mut cursors = self.cursors.clone();
+
+        cursors.push(self.get_primary_cursor());
+        cursors.sort_by_key(
+            |cursor| {
+                return cursor.location;
+            }
+        );
+
+        return cursors;
+    }
+
+    fn collapse_cursors(&mut self) {
+        self.block_selection = None;
+
+        if !self.cursors.is_empty() {
+            self.cursors.clear();
+            self.set_needs_redraw(true);
+        }
+    }
+
+    pub fn dismiss(&mut self) {
+        self.collapse_cursors();
+        self.clear_selection();
+    }
+
+    pub fn add_cursor_above(&mut self) -> bool {
+        let Some(topmost) = self.get_cursors().first().copied() else {
+            return false;
+        };
+
+        if topmost.location.line_index == 0 {
+            return false;
+        }
+
+        let location = self.get_location_at_same_column(topmost.location, topmost.location.line_index.saturating_sub(1));
+
+        self.add_cursor(location, None);
+
+        return true;
+    }
+
+    pub fn add_cursor_below(&mut self) -> bool {
+        let Some(bottommost) = self.get_cursors().last().copied() else {
+            return false;
+        };
+        let line_index = bottommost.location.line_index.saturating_add(1);
+
+        if line_index >= self.buffer.height() {
+            return false;
+        }
+
+        let location = self.get_location_at_same_column(bottommost.location, line_index);
+
+        self.add_cursor(location, None);
+
+        return true;
+    }
+
+    fn get_location_at_same_column(&self, from: Location, line_index: usize) -> Location {
+        let tab_width = self.indentation.tab_width;
+        let column = self.buffer.lines.get(from.line_index).map_or(0, |line| { return line.width_until(from.grapheme_index, tab_width); });
+        let grapheme_index = self.buffer.lines.get(line_index).map_or(0, |line| { return line.grapheme_index_at_column(column, tab_width); });
+
+        return Location {
+            line_index,
+            grapheme_index,
+        };
+    }
+
+    fn add_cursor(&mut self, location: Location, anchor: Option<Location>) {
+        self.block_selection = None;
+        self.cursors.push(self.get_primary_cursor());
+        self.text_location = location;
+        self.selection_anchor = anchor;
+
+        self.scroll_text_location_into_view();
+        self.set_needs_redraw(true);
+    }
+
+    pub fn add_cursor_at_next_match(&mut self) -> bool {
+        let Some((start, end)) = self.get_selection_range() else {
+            let mut selected = false;
+
+            self.for_each_cursor(
+                |view, _| {
+                    selected |= view.select_word_under_cursor();
+                }
+            );
+
+            return selected;
+        };
+
+        if start.line_index != end.line_index {
+            return false;
+        }
+
+        let query = self.buffer.get_text(start, end);
+        let Some(match_start) = self.buffer.search_next(&query, end) else {
+            return false;
+        };
+        let is_selected = self
+            .get_cursors()
+            .iter()
+            .any(
+                |cursor| {
+                    return cursor
+                        .get_selection_range()
+                        .is_some_and(
+                            |(selection_start, _)| {
+                                return selection_start == match_start;
+                            }
+                        );
+                }
+            );
+
+        if is_selected {
+            return false;
+        }
+
+        let match_end = Location {
+            line_index: match_start.line_index,
+            grapheme_index: match_start.grapheme_index.saturating_add(Line::from(&query).grapheme_count()),
+        };
+
+        self.add_cursor(match_end, Some(match_start));
+
+        return true;
+    }
+
+    fn get_word_range_under_cursor(&self) -> Option<(Location, Location)> {
+        let range = self
+            .buffer
+            .lines
+            .get(self.text_location.line_index)?
+            .get_word_range(self.text_location.grapheme_index, self.subword_mode)?;
+
+        return Some(
+            (
+                Location {
+                    line_index: self.text_location.line_index,
+                    grapheme_index: range.start,
+                },
+                Location {
+                    line_index: self.text_location.line_index,
+                    grapheme_index: range.end,
+                },
+            )
+        );
+    }
+
+    fn select_word_under_cursor(&mut self) -> bool {
+        let Some((start, end)) = self.get_word_range_under_cursor() else {
+            return false;
+        };
+
+        self.selection_anchor = Some(start);
+        self.text_location = end;
+
+        self.set_needs_redraw(true);
+
+        return true;
+    }
+
+    pub fn select_all_occurrences(&mut self) -> usize {
+        if self.get_selection_range().is_none() {
+            self.collapse_cursors();
+            self.select_word_under_cursor();
+        }
+
+        let Some((start, end)) = self.get_selection_range() else {
+            return 0;
+        };
+        let query = self.buffer.get_text(start, end);
+
+        return self.select_all_matches(&query);
+    }
+
+    pub fn select_all_matches(&mut self, query: &str) -> usize {
+        let mut cursors: Vec<Cursor> = self
+            .buffer
+            .find_all(query)
+            .into_iter()
+            .map(
+                |(start, end)| {
+                    return Cursor {
+                        location: end,
+                        anchor: Some(start),
+                    };
+                }
+            )
+            .collect();
+
+        if cursors.is_empty() {
+            return 0;
+        }
+
+        let primary_index = cursors
+            .iter()
+            .position(
+                |cursor| {
+                    return cursor.location >= self.text_location;
+                }
+            )
+            .unwrap_or(0);
+        let primary = cursors.remove(primary_index);
+
+        self.text_location = primary.location;
+        self.selection_anchor = primary.anchor;
+        self.cursors = cursors;
+        self.block_selection = None;
+
+        self.scroll_text_location_into_view();
+        self.set_needs_redraw(true);
+
+        return self.cursors.len().saturating_add(1);
+    }
+
+    pub fn handle_select_block_command(&mut self, command: Move) {
+        let mut block = self.block_selection.unwrap_or_else(
+            || {
+                let position = self.text_location_to_position();
+
+                return BlockSelection {
+                    anchor: position,
+                    head: position,
+                };
+            }
+        );
+        let last_line_index = self.buffer.height().saturating_sub(1);
+        let page_height = self.size.height.saturating_sub(1);
+
+        match command {
+            Move::Up => {
+                block.head.row = block.head.row.saturating_sub(1);
+            },
+            Move::Down => {
+                block.head.row = min(block.head.row.saturating_add(1), last_line_index);
+            },
+            Move::PageUp => {
+                block.head.row = block.head.row.saturating_sub(page_height);
+            },
+            Move::PageDown => {
+                block.head.row = min(block.head.row.saturating_add(page_height), last_line_index);
+            },
+            Move::Left => {
+                block.head.column = self.get_block_column_before(block.head);
+            },
+            Move::Right => {
+                block.head.column = self.get_block_column_after(block);
+            },
+            Move::StartOfLine => {
+                block.head.column = 0;
+            },
+            Move::EndOfLine => {
+                block.head.column = self.buffer.lines.get(block.head.row).map_or(0, |line| { return line.width(self.indentation.tab_width); });
+            },
+            Move::WordLeft
+            | Move::WordRight
+            | Move::ParagraphUp
+            | Move::ParagraphDown
+            | Move::StartOfFile
+            | Move::EndOfFile
+            | Move::MatchingBracket => {},
+        }
+
+        self.apply_block_selection(block);
+    }
+
+    fn get_block_column_before(&self, head: Position) -> Column {
+        let column = head.column.saturating_sub(1);
+        let tab_width = self.indentation.tab_width;
+
+        return self
+            .buffer
+            .lines
+            .get(head.row)
+            .filter(
+                |line| {
+                    return column < line.width(tab_width);
+                }
+            )
+            .map_or(
+                column,
+                |line| {
+                    return line.width_until(line.grapheme_index_at_column(column, tab_width), tab_width);
+                }
+            );
+    }
+
+    fn get_block_column_after(&self, block: BlockSelection) -> Column {
+        let column = block.head.column.saturating_add(1);
+        let top = min(block.anchor.row, block.head.row);
+        let bottom = max(block.anchor.row, block.head.row);
+        let tab_width = self.indentation.tab_width;
+        let max_width = self
+            .buffer
+            .lines
+            .iter()
+            .skip(top)
+            .take(bottom.saturating_sub(top).saturating_add(1))
+            .map(
+                |line| {
+                    return line.width(tab_width);
+                }
+            )
+            .max()
+            .unwrap_or(0);
+
+        return self
+            .buffer
+            .lines
+            .get(block.head.row)
+            .filter(
+                |line| {
+                    return column < line.width(tab_width);
+                }
+            )
+            .map_or(
+                min(column, max(max_width, block.head.column)),
+                |line| {
+                    return line.width_until(line.grapheme_index_from_column(column, tab_width), tab_width);
+                }
+            );
+    }
+
+    fn apply_block_selection(&mut self, block: BlockSelection) {
+        let top = min(block.anchor.row, block.head.row);
+        let bottom = max(block.anchor.row, block.head.row);
+        let left = min(block.anchor.column, block.head.column);
+        let right = max(block.anchor.column, block.head.column);
+        let mut primary = Cursor::default();
+        let mut cursors = Vec::new();
+
+        for line_index in top..=bottom {
+            let Some(line) = self.buffer.lines.get(line_index) else {
+                continue;
+            };
+
+            if line.width(self.indentation.tab_width) < left && line_index != block.head.row {
+                continue;
+            }
+
+            let start = Location {
+                line_index,
+                grapheme_index: line.grapheme_index_at_column(left, self.indentation.tab_width),
+            };
+            let end = Location {
+                line_index,
+                grapheme_index: line.grapheme_index_from_column(right, self.indentation.tab_width),
+            };
+            let (anchor, location) = if block.head.column >= block.anchor.column {
+                (start, end)
+            } else {
+                (end, start)
+            };
+            let cursor = Cursor {
+                location,
+                anchor: (start != end).then_some(anchor),
+            };
+
+            if line_index == block.head.row {
+                primary = cursor;
+            } else {
+                cursors.push(cursor);
+            }
+        }
+
+        self.text_location = primary.location;
+        self.selection_anchor = primary.anchor;
+        self.cursors = cursors;
+        self.block_selection = Some(block);
+
+        self.scroll_text_location_into_view();
+        self.set_needs_redraw(true);
+    }
+
+    fn move_text_location(&mut self, command: Move) {
         let Size {
             width: _,
             height
@@ -105,222 +780,1910 @@ impl View {
             Move::EndOfLine => {
                 self.move_to_end_of_line();
             },
+            Move::WordLeft => {
+                self.text_location = self.buffer.get_previous_word_location(self.text_location, self.subword_mode);
+            },
+            Move::WordRight => {
+                self.text_location = self.buffer.get_next_word_location(self.text_location, self.subword_mode);
+            },
+            Move::ParagraphUp => {
+                self.text_location = self.buffer.get_previous_paragraph_location(self.text_location);
+            },
+            Move::ParagraphDown => {
+                self.text_location = self.buffer.get_next_paragraph_location(self.text_location);
+            },
+            Move::StartOfFile => {
+                self.text_location = Location::default();
+            },
+            Move::EndOfFile => {
+                self.text_location = self.buffer.end_location();
+            },
+            Move::MatchingBracket => {
+                if let Some((_, location)) = self.get_bracket_pair() {
+                    self.text_location = location;
+                }
+            },
+        }
+
+        self.scroll_text_location_into_view();
+    }
+
+    pub fn apply_config(&mut self, config: &Config) {
+        self.indentation = Indentation::from(config);
+        self.auto_pair = config.auto_pair;
+        self.text_width = config.text_width;
+        self.auto_wrap = config.auto_wrap;
+        self.fallback_encoding = config.fallback_encoding;
+    }
+
+    pub fn load(&mut self, file: &str) -> Result<(), Error> {
+        self.buffer = Buffer::load(file, self.fallback_encoding)?;
+        self.jump_list = LocationList::default();
+        self.change_list = LocationList::default();
+        self.marks = Marks::default();
+        self.bracket_pair_key = None;
+        self.indentation.detect(&self.buffer.lines);
+        self.set_needs_redraw(true);
+
+        return Ok(());
+    }
+
+    pub fn reopen_as(&mut self, encoding: Encoding) -> Result<(), Error> {
+        let Some(file) = self.buffer.file_info.get_path().and_then(Path::to_str) else {
+            return Err(Error::other("No file open"));
+        };
+
+        self.buffer = Buffer::load_as(file, encoding)?;
+        self.bracket_pair_key = None;
+        self.collapse_cursors();
+        self.clear_selection();
+        self.snap_to_valid_line();
+        self.snap_to_valid_grapheme();
+        self.scroll_text_location_into_view();
+        self.set_needs_redraw(true);
+
+        return Ok(());
+    }
+
+    pub const fn has_normalized_line_endings(&self) -> bool {
+        return self.buffer.has_normalized_line_endings;
+    }
+
+    pub fn set_encoding(&mut self, encoding: Encoding) -> bool {
+        return self.buffer.set_encoding(encoding);
+    }
+
+    pub fn read_lines(&self, file_path: &Path) -> Result<Vec<String>, Error> {
+        let Some(file) = file_path.to_str() else {
+            return Err(Error::other("Invalid file path"));
+        };
+        let buffer = Buffer::load_as(file, self.buffer.encoding)?;
+
+        return Ok(buffer.get_lines(0..=buffer.height()));
+    }
+
+    pub fn get_all_lines(&self) -> Vec<String> {
+        return self.buffer.get_lines(0..=self.buffer.height());
+    }
+
+    pub fn replace_all_lines(&mut self, lines: &[String]) {
+        let location_before = self.text_location;
+
+        self.collapse_cursors();
+        self.clear_selection();
+
+        if self.buffer.height() == 0 {
+            self.buffer.insert_text(&lines.join("\n"), Location::default());
+        } else {
+            self.buffer.replace_lines(0..=self.buffer.height(), lines);
+        }
+
+        self.snap_to_valid_line();
+        self.snap_to_valid_grapheme();
+        self.scroll_text_location_into_view();
+        self.set_needs_redraw(true);
+        self.commit_edit(location_before);
+    }
+
+    pub const fn is_file_loaded(&self) -> bool {
+        return self.buffer.is_file_loaded();
+    }
+
+    pub fn save(&mut self) -> Result<(), Error> {
+        return self.buffer.save();
+    }
+
+    pub fn save_as(&mut self, file_name: &str) -> Result<(), Error> {
+        return self.buffer.save_as(file_name);
+    }
+
+    pub fn set_line_ending(&mut self, line_ending: LineEnding) -> bool {
+        return self.buffer.set_line_ending(line_ending);
+    }
+
+    pub fn toggle_final_newline(&mut self) -> bool {
+        return self.buffer.toggle_final_newline();
+    }
+
+    pub fn get_cursor_position(&self) -> Position {
+        let position = self.text_location_to_position().saturating_sub(self.scroll_offset);
+
+        return Position {
+            column: position.column.saturating_add(self.get_gutter_width()),
+            row: position.row,
+        };
+    }
+
+    fn get_gutter_width(&self) -> usize {
+        if self.marks.is_empty() {
+            return 0;
+        }
+
+        return MARK_GUTTER_WIDTH;
+    }
+
+    fn get_text_area_width(&self) -> usize {
+        return self.size.width.saturating_sub(self.get_gutter_width());
+    }
+
+    fn center_text_location(&mut self) {
+        let width = self.get_text_area_width();
+        let height = self.size.height;
+        let Position {
+            column,
+            row
+        } = self.text_location_to_position();
+
+        let horizontal_mid = width.div_ceil(2);
+        let vertical_mid = height.div_ceil(2);
+
+        self.scroll_offset.column = column.saturating_sub(horizontal_mid);
+        self.scroll_offset.row = row.saturating_sub(vertical_mid);
+
+        self.set_needs_redraw(true);
+    }
+
+    pub fn go_to(&mut self, query: &str) -> bool {
+        if let Some(location) = self.parse_goto_location(query) {
+            let location_before = self.text_location;
+
+            self.collapse_cursors();
+            self.clear_selection();
+            self.text_location = location;
+            self.snap_to_valid_grapheme();
+            self.center_text_location();
+            self.record_jump(location_before);
+
+            return true;
+        }
+
+        return false;
+    }
+
+    fn record_jump(&mut self, from: Location) {
+        if from.line_index != self.text_location.line_index {
+            self.jump_list.push(from);
+        }
+    }
+
+    pub fn jump_back(&mut self) -> bool {
+        if self.jump_list.is_at_end() {
+            self.jump_list.push(self.text_location);
+        }
+
+        if let Some(location) = self.jump_list.previous(self.text_location) {
+            self.jump_to(location);
+
+            return true;
+        }
+
+        return false;
+    }
+
+    pub fn jump_forward(&mut self) -> bool {
+        if let Some(location) = self.jump_list.next(self.text_location) {
+            self.jump_to(location);
+
+            return true;
+        }
+
+        return false;
+    }
+
+    pub fn go_to_previous_change(&mut self) -> bool {
+        if let Some(location) = self.change_list.previous(self.text_location) {
+            self.jump_to(location);
+
+            return true;
+        }
+
+        return false;
+    }
+
+    pub fn go_to_next_change(&mut self) -> bool {
+        if let Some(location) = self.change_list.next(self.text_location) {
+            self.jump_to(location);
+
+            return true;
+        }
+
+        return false;
+    }
+
+    pub fn set_mark(&mut self, name: char) -> Location {
+        self.set_mark_at(name, self.text_location);
+
+        return self.text_location;
+    }
+
+    pub fn set_mark_at(&mut self, name: char, location: Location) {
+        self.marks.set(name, location);
+        self.scroll_text_location_into_view();
+        self.set_needs_redraw(true);
+    }
+
+    pub fn jump_to_mark(&mut self, name: char) -> bool {
+        if let Some(location) = self.marks.get(name) {
+            let location_before = self.text_location;
+
+            self.jump_to(location);
+            self.record_jump(location_before);
+
+            return true;
+        }
+
+        return false;
+    }
+
+    pub fn get_mark_label(&self, name: char) -> Option<String> {
+        let location = self.marks.get(name)?;
+        let text = self.buffer.lines
+            .get(location.line_index)
+            .map(Line::to_string)
+            .unwrap_or_default();
+
+        return Some(
+            format!(
+                "{}:{}  {}",
+                location.line_index.saturating_add(1),
+                location.grapheme_index.saturating_add(1),
+                text.trim(),
+            )
+        );
+    }
+
+    pub fn get_global_marks(&self) -> Vec<(char, Location)> {
+        return self.marks.get_global();
+    }
+
+    pub fn get_file_path(&self) -> Option<&Path> {
+        return self.buffer.file_info.get_path();
+    }
+
+    fn jump_to(&mut self, location: Location) {
+        self.overwritten.clear();
+        self.collapse_cursors();
+        self.clear_selection();
+        self.text_location = location;
+        self.snap_to_valid_line();
+        self.snap_to_valid_grapheme();
+        self.center_text_location();
+    }
+
+    fn parse_goto_location(&self, query: &str) -> Option<Location> {
+        let query = query.trim();
+        let last_line_index = self.buffer.height().saturating_sub(1);
+
+        if let Some(percentage) = query.strip_suffix('%') {
+            let percentage: usize = percentage.trim().parse().ok()?;
+
+            return Some(
+                Location {
+                    line_index: last_line_index.saturating_mul(min(percentage, 100)).div_ceil(100),
+                    grapheme_index: 0,
+                }
+            );
+        }
+
+        let (line_part, column_part) = query
+            .split_once(':')
+            .map_or(
+                (query, None),
+                |(line_part, column_part)| {
+                    return (line_part, Some(column_part));
+                }
+            );
+        let line_part = line_part.trim();
+        let line_index = if let Some(offset) = line_part.strip_prefix('+') {
+            self.text_location.line_index.saturating_add(offset.trim().parse().ok()?)
+        } else if let Some(offset) = line_part.strip_prefix('-') {
+            self.text_location.line_index.saturating_sub(offset.trim().parse().ok()?)
+        } else {
+            line_part.parse::<usize>().ok()?.saturating_sub(1)
+        };
+        let grapheme_index = match column_part {
+            Some(column_part) => {
+                column_part.trim().parse::<usize>().ok()?.saturating_sub(1)
+            },
+            None => {
+                0
+            },
+        };
+
+        return Some(
+            Location {
+                line_index: min(line_index, last_line_index),
+                grapheme_index,
+            }
+        );
+    }
+
+    fn text_location_to_position(&self) -> Position {
+        let column = self
+            .buffer
+            .lines
+            .get(
+                self.text_location.line_index
+            )
+            .map_or(
+                0,
+                |line| {
+                    return line.width_until(self.text_location.grapheme_index, self.indentation.tab_width);
+                }
+            );
+        let row = self.text_location.line_index;
+
+        return Position {
+            column,
+            row,
+        };
+    }
+
+    fn move_up(&mut self, step: usize) {
+        self.text_location.line_index = self.text_location.line_index.saturating_sub(step);
+        self.snap_to_valid_grapheme();
+    }
+
+    fn move_down(&mut self, step: usize) {
+        self.text_location.line_index = self.text_location.line_index.saturating_add(step);
+        self.snap_to_valid_grapheme();
+        self.snap_to_valid_line();
+    }
+
+    fn move_right(&mut self) {
+        let line_width = self.buffer.lines.get(self.text_location.line_index).map_or(0, Line::grapheme_count);
+        if self.text_location.grapheme_index < line_width {
+            self.text_location.grapheme_index += 1;
+        } else {
+            self.move_to_start_of_line();
+            self.move_down(1);
+        }
+    }
+
+    fn move_left(&mut self) {
+        if self.text_location.grapheme_index > 0 {
+            self.text_location.grapheme_index -= 1;
+        } else if self.text_location.line_index > 0 {
+            self.move_up(1);
+            self.move_to_end_of_line();
+        }
+    }
+
+    fn move_to_start_of_line(&mut self) {
+        self.text_location.grapheme_index = 0;
+    }
+
+    fn move_to_end_of_line(&mut self) {
+        self.text_location.grapheme_index = self.buffer.lines.get(self.text_location.line_index).map_or(0, Line::grapheme_count);
+    }
+
+    fn snap_to_valid_grapheme(&mut self) {
+        self.text_location.grapheme_index = self.buffer.lines.get(self.text_location.line_index).map_or(0, |line| { min(line.grapheme_count(), self.text_location.grapheme_index) });
+    }
+
+    fn snap_to_valid_line(&mut self) {
+        self.text_location.line_index = min(self.text_location.line_index, self.buffer.height());
+    }
+
+    fn scroll_horizontally(&mut self, to_where: Column) {
+        let width = self.get_text_area_width();
+        let offset_changed = if to_where < self.scroll_offset.column {
+            self.scroll_offset.column = to_where;
+
+            true
+        } else if to_where >= self.scroll_offset.column.saturating_add(width) {
+            self.scroll_offset.column = to_where.saturating_sub(width).saturating_add(1);
+
+            true
+        } else {
+            false
+        };
+
+        if offset_changed {
+            self.set_needs_redraw(true);
+        }
+    }
+
+    fn scroll_vertically(&mut self, to_where: Row) {
+        let Size {
+            width: _,
+            height
+        } = self.size;
+        let offset_changed = if to_where < self.scroll_offset.row {
+            self.scroll_offset.row = to_where;
+
+            true
+        } else if to_where >= self.scroll_offset.row.saturating_add(height) {
+            self.scroll_offset.row = to_where.saturating_sub(height).saturating_add(1);
+
+            true
+        } else {
+            false
+        };
+
+        if offset_changed {
+            self.set_needs_redraw(true);
+        }
+    }
+
+    fn scroll_text_location_into_view(&mut self) {
+        let Position {
+            column,
+            row
+        } = self.text_location_to_position();
+
+        self.scroll_horizontally(column);
+        self.scroll_vertically(row);
+    }
+
+    fn type_char(&mut self, character: char) {
+        if self.auto_pair {
+            let next_character = self.get_char_at(self.text_location);
+
+            if matches!(character, ')' | ']' | '}' | '"' | '\'') && next_character == Some(character) {
+                self.move_cursor(Move::Right);
+
+                return;
+            }
+
+            if let Some(closer) = self.get_auto_closer(character) {
+                self.insert_char(character);
+                self.buffer.insert_text(&closer.to_string(), self.text_location);
+
+                return;
+            }
+        }
+
+        self.insert_char(character);
+    }
+
+    fn get_auto_closer(&self, character: char) -> Option<char> {
+        let closer = Self::get_closer(character)?;
+        let next_character = self.get_char_at(self.text_location);
+        let is_next_free = next_character.is_none_or(
+            |next_character| {
+                return next_character.is_whitespace() || matches!(next_character, ')' | ']' | '}' | ',' | ';');
+            }
+        );
+
+        if !is_next_free {
+            return None;
+        }
+
+        if character == '"' || character == '\'' {
+            let previous_character = self
+                .text_location
+                .grapheme_index
+                .checked_sub(1)
+                .and_then(
+                    |grapheme_index| {
+                        return self.get_char_at(
+                            Location {
+                                line_index: self.text_location.line_index,
+                                grapheme_index,
+                            }
+                        );
+                    }
+                );
+
+            if previous_character.is_some_and(|previous_character| { return previous_character.is_alphanumeric() || previous_character == character; }) {
+                return None;
+            }
+        }
+
+        return Some(closer);
+    }
+
+    const fn get_closer(character: char) -> Option<char> {
+        match character {
+            '(' => {
+                return Some(')');
+            },
+            '[' => {
+                return Some(']');
+            },
+            '{' => {
+                return Some('}');
+            },
+            '"' | '\'' => {
+                return Some(character);
+            },
+            _ => {
+                return None;
+            },
+        }
+    }
+
+    fn get_char_at(&self, location: Location) -> Option<char> {
+        return self
+            .buffer
+            .lines
+            .get(location.line_index)?
+            .substr(location.grapheme_index..location.grapheme_index.saturating_add(1))
+            .chars()
+            .next();
+    }
+
+    fn wrap_selection(&mut self, character: char) -> bool {
+        if !self.auto_pair {
+            return false;
+        }
+
+        let (Some(closer), Some((start, end))) = (Self::get_closer(character), self.get_selection_range()) else {
+            return false;
+        };
+
+        self.buffer.insert_text(&closer.to_string(), end);
+        self.buffer.insert_text(&character.to_string(), start);
+
+        let inner_start = Location {
+            line_index: start.line_index,
+            grapheme_index: start.grapheme_index.saturating_add(1),
+        };
+        let inner_end = if end.line_index == start.line_index {
+            Location {
+                line_index: end.line_index,
+                grapheme_index: end.grapheme_index.saturating_add(1),
+            }
+        } else {
+            end
+        };
+
+        if self.text_location == end {
+            self.selection_anchor = Some(inner_start);
+            self.text_location = inner_end;
+        } else {
+            self.selection_anchor = Some(inner_end);
+            self.text_location = inner_start;
+        }
+
+        self.scroll_text_location_into_view();
+        self.set_needs_redraw(true);
+
+        return true;
+    }
+
+    fn delete_empty_pair(&mut self) -> bool {
+        if !self.auto_pair || self.text_location.grapheme_index == 0 {
+            return false;
+        }
+
+        let start = Location {
+            line_index: self.text_location.line_index,
+            grapheme_index: self.text_location.grapheme_index.saturating_sub(1),
+        };
+        let is_empty_pair = self
+            .get_char_at(start)
+            .and_then(Self::get_closer)
+            .is_some_and(
+                |closer| {
+                    return self.get_char_at(self.text_location) == Some(closer);
+                }
+            );
+
+        if !is_empty_pair {
+            return false;
+        }
+
+        self.buffer.remove_text(
+            start,
+            Location {
+                line_index: self.text_location.line_index,
+                grapheme_index: self.text_location.grapheme_index.saturating_add(1),
+            },
+        );
+        self.text_location = start;
+
+        self.scroll_text_location_into_view();
+        self.set_needs_redraw(true);
+
+        return true;
+    }
+
+    fn get_cached_bracket_pair(&mut self) -> Option<(Location, Location)> {
+        let key = Some((self.text_location, self.buffer.get_revision()));
+
+        if self.bracket_pair_key != key {
+            self.bracket_pair = self.get_bracket_pair();
+            self.bracket_pair_key = key;
+        }
+
+        return self.bracket_pair;
+    }
+
+    fn get_bracket_pair(&self) -> Option<(Location, Location)> {
+        let before = self
+            .text_location
+            .grapheme_index
+            .checked_sub(1)
+            .map(
+                |grapheme_index| {
+                    return Location {
+                        line_index: self.text_location.line_index,
+                        grapheme_index,
+                    };
+                }
+            );
+
+        return [Some(self.text_location), before]
+            .into_iter()
+            .flatten()
+            .find_map(
+                |location| {
+                    return self
+                        .buffer
+                        .find_matching_bracket(location)
+                        .map(
+                            |matching_location| {
+                                return (location, matching_location);
+                            }
+                        );
+                }
+            );
+    }
+
+    fn insert_char(&mut self, character: char) {
+        if self.indentation.auto_indent && matches!(character, ')' | ']' | '}') {
+            self.dedent_before_closing_bracket();
+        }
+
+        let old_length = self.buffer.lines.get(self.text_location.line_index).map_or(0, Line::grapheme_count);
+
+        self.buffer.insert_char(
+            character,
+            self.text_location
+        );
+
+        let new_length = self.buffer.lines.get(self.text_location.line_index).map_or(0, Line::grapheme_count);
+
+        let grapheme_delta = new_length.saturating_sub(old_length);
+
+        if grapheme_delta > 0 {
+            self.move_cursor(Move::Right);
+
+            if !character.is_whitespace() && self.auto_wrap && self.is_prose_file() {
+                self.auto_wrap_line();
+            }
+        }
+
+        self.set_needs_redraw(true);
+    }
+
+    fn is_prose_file(&self) -> bool {
+        let Some(path) = self.buffer.file_info.get_path() else {
+            return false;
+        };
+
+        if path.file_name().is_some_and(|file_name| { return file_name == "COMMIT_EDITMSG"; }) {
+            return true;
+        }
+
+        return path
+            .extension()
+            .and_then(|extension| { return extension.to_str(); })
+            .is_some_and(
+                |extension| {
+                    return PROSE_EXTENSIONS.contains(&extension.to_lowercase().as_str());
+                }
+            );
+    }
+
+    fn auto_wrap_line(&mut self) {
+        let text_width = self.get_text_width();
+        let line_index = self.text_location.line_index;
+        let Some(line) = self.buffer.lines.get(line_index) else {
+            return;
+        };
+
+        let columns = line.get_fragment_columns(self.indentation.tab_width);
+
+        if columns.last().is_none_or(|width| { return *width <= text_width; }) {
+            return;
+        }
+
+        let line_string = line.to_string();
+        let prefix = Reflower::get_prefix(&line_string);
+        let prefix_length = Line::from(prefix).grapheme_count();
+        let is_whitespace_at = |grapheme_index: usize| {
+            return line
+                .substr(grapheme_index..grapheme_index.saturating_add(1))
+                .chars()
+                .all(char::is_whitespace);
+        };
+        let break_starts = (prefix_length.saturating_add(1)..self.text_location.grapheme_index)
+            .filter(
+                |grapheme_index| {
+                    return is_whitespace_at(*grapheme_index) && !is_whitespace_at(grapheme_index.saturating_sub(1));
+                }
+            )
+            .collect::<Vec<usize>>();
+        let Some(break_start) = break_starts
+            .iter()
+            .rev()
+            .find(
+                |grapheme_index| {
+                    return columns[**grapheme_index] <= text_width;
+                }
+            )
+            .or(break_starts.first())
+            .copied() else {
+            return;
+        };
+        let break_end = (break_start..self.text_location.grapheme_index)
+            .find(
+                |grapheme_index| {
+                    return !is_whitespace_at(*grapheme_index);
+                }
+            )
+            .unwrap_or(self.text_location.grapheme_index);
+        let continuation = format!("\n{prefix}");
+        let pending_changes_count = self.buffer.get_pending_changes().len();
+        let start = Location {
+            line_index,
+            grapheme_index: break_start,
+        };
+
+        self.buffer.remove_text(
+            start,
+            Location {
+                line_index,
+                grapheme_index: break_end,
+            },
+        );
+        self.buffer.insert_text(&continuation, start);
+        self.transform_primary_cursor(pending_changes_count);
+    }
+
+    fn overwrite_char(&mut self, character: char) {
+        let location = self.text_location;
+        let grapheme_count = self.buffer.lines.get(location.line_index).map_or(0, Line::grapheme_count);
+        let replaced = (location.grapheme_index < grapheme_count).then(
+            || {
+                return self.buffer.remove_text(
+                    location,
+                    Location {
+                        line_index: location.line_index,
+                        grapheme_index: location.grapheme_index.saturating_add(1),
+                    },
+                );
+            }
+        );
+
+        self.insert_char(character);
+
+        if self.text_location == location {
+            if let Some(replaced) = replaced {
+                self.buffer.insert_text(&replaced, self.text_location);
+            }
+
+            return;
+        }
+
+        let start = Location {
+            line_index: self.text_location.line_index,
+            grapheme_index: self.text_location.grapheme_index.saturating_sub(1),
+        };
+
+        self.overwritten.push(
+            (
+                start,
+                self.buffer.get_text(start, self.text_location),
+                replaced,
+            )
+        );
+    }
+
+    fn restore_overwritten(&mut self) {
+        let Some(grapheme_index) = self.text_location.grapheme_index.checked_sub(1) else {
+            self.move_cursor(Move::Left);
+
+            return;
+        };
+        let start = Location {
+            line_index: self.text_location.line_index,
+            grapheme_index,
+        };
+        let typed = self.buffer.get_text(start, self.text_location);
+        let Some(index) = self.overwritten.iter().rposition(
+            |(location, text, _)| {
+                return *location == start && *text == typed;
+            }
+        ) else {
+            self.move_cursor(Move::Left);
+
+            return;
+        };
+        let (_, _, replaced) = self.overwritten.remove(index);
+
+        self.buffer.remove_text(start, self.text_location);
+
+        if let Some(replaced) = replaced {
+            self.buffer.insert_text(&replaced, start);
+        }
+
+        self.text_location = start;
+
+        self.scroll_text_location_into_view();
+        self.set_needs_redraw(true);
+    }
+
+    pub fn toggle_overwrite_mode(&mut self) -> bool {
+        self.overwrite_mode = !self.overwrite_mode;
+        self.overwritten.clear();
+
+        return self.overwrite_mode;
+    }
+
+    pub const fn is_overwrite_mode(&self) -> bool {
+        return self.overwrite_mode;
+    }
+
+    pub fn transform(&mut self, transform: Transform) -> Result<(), String> {
+        self.transform_error = None;
+        self.handle_edit_command(Edit::Transform(transform));
+
+        return self.transform_error.take().map_or(Ok(()), Err);
+    }
+
+    fn transform_text(&mut self, transform: Transform) {
+        let selection = self.get_selection_range();
+        let Some((start, end)) = selection.or_else(|| { return self.get_word_range_under_cursor(); }) else {
+            return;
+        };
+        let text = self.buffer.get_text(start, end);
+        let result = match Transformer::apply(transform, &text) {
+            Ok(result) => {
+                result
+            },
+            Err(error) => {
+                self.transform_error = Some(error);
+
+                return;
+            },
+        };
+
+        if Transformer::keeps_text(transform) {
+            let digest_end = self.buffer.insert_text(&format!(" {result}"), end);
+
+            self.selection_anchor = selection.map(
+                |_| {
+                    return Location {
+                        line_index: end.line_index,
+                        grapheme_index: end.grapheme_index.saturating_add(1),
+                    };
+                }
+            );
+            self.text_location = digest_end;
+        } else if result != text {
+            self.buffer.remove_text(start, end);
+
+            let new_end = self.buffer.insert_text(&result, start);
+
+            if selection.is_some() {
+                self.selection_anchor = Some(start);
+                self.text_location = new_end;
+            } else {
+                self.text_location = min(self.text_location, new_end);
+            }
+        } else {
+            return;
+        }
+
+        self.scroll_text_location_into_view();
+        self.set_needs_redraw(true);
+    }
+
+    fn insert_tab(&mut self) {
+        if self.indentation.hard_tabs {
+            self.insert_char('\t');
+
+            return;
+        }
+
+        let column = self.text_location_to_position().column;
+        let spaces_count = self.indentation.width.saturating_sub(column % self.indentation.width);
+
+        self.text_location = self.buffer.insert_text(&" ".repeat(spaces_count), self.text_location);
+
+        self.scroll_text_location_into_view();
+        self.set_needs_redraw(true);
+    }
+
+    fn indent_lines(&mut self) {
+        let pending_changes_count = self.buffer.get_pending_changes().len();
+        let unit = self.indentation.get_unit();
+
+        for line_index in self.get_selected_lines() {
+            if self.buffer.lines.get(line_index).is_some_and(|line| { return !line.trim().is_empty(); }) {
+                self.buffer.insert_text(
+                    &unit,
+                    Location {
+                        line_index,
+                        grapheme_index: 0,
+                    },
+                );
+            }
+        }
+
+        self.transform_primary_cursor(pending_changes_count);
+    }
+
+    fn dedent_lines(&mut self) {
+        let pending_changes_count = self.buffer.get_pending_changes().len();
+
+        for line_index in self.get_selected_lines() {
+            let dedent_length = self
+                .buffer
+                .lines
+                .get(line_index)
+                .map_or(
+                    0,
+                    |line| {
+                        return self.indentation.get_leading_dedent_length(line);
+                    }
+                );
+
+            if dedent_length > 0 {
+                self.buffer.remove_text(
+                    Location {
+                        line_index,
+                        grapheme_index: 0,
+                    },
+                    Location {
+                        line_index,
+                        grapheme_index: dedent_length,
+                    },
+                );
+            }
+        }
+
+        self.transform_primary_cursor(pending_changes_count);
+    }
+
+    fn transform_primary_cursor(&mut self, pending_changes_count: usize) {
+        let mut cursor = self.get_primary_cursor();
+
+        for change in self.buffer.get_pending_changes().iter().skip(pending_changes_count) {
+            cursor.transform(change);
+        }
+
+        self.text_location = cursor.location;
+        self.selection_anchor = cursor.anchor;
+
+        self.scroll_text_location_into_view();
+        self.set_needs_redraw(true);
+    }
+
+    fn insert_line(&mut self) {
+        let auto_indentation = self.get_auto_indentation();
+
+        self.buffer.insert_line(self.text_location);
+        self.move_cursor(Move::Right);
+
+        if let Some((indentation, closing_indentation)) = auto_indentation {
+            self.text_location = self.buffer.insert_text(&indentation, self.text_location);
+
+            if let Some(closing_indentation) = closing_indentation {
+                self.buffer.insert_text(&format!("\n{closing_indentation}"), self.text_location);
+            }
+
+            self.scroll_text_location_into_view();
+        }
+
+        self.set_needs_redraw(true);
+    }
+
+    fn get_auto_indentation(&self) -> Option<(String, Option<String>)> {
+        if !self.indentation.auto_indent {
+            return None;
+        }
+
+        let line = self.buffer.lines.get(self.text_location.line_index)?;
+        let before = line.substr(0..self.text_location.grapheme_index);
+        let after = line.substr(self.text_location.grapheme_index..line.grapheme_count());
+        let base_indentation: String = before
+            .chars()
+            .take_while(
+                |character| {
+                    return character.is_whitespace();
+                }
+            )
+            .collect();
+        let opener = before.trim_end().chars().last();
+
+        if !matches!(opener, Some('(' | '[' | '{' | ':')) {
+            return Some((base_indentation, None));
+        }
+
+        let closer = after.trim_start().chars().next();
+        let closes_block = matches!(
+            (opener, closer),
+            (Some('('), Some(')')) | (Some('['), Some(']')) | (Some('{'), Some('}'))
+        );
+
+        return Some(
+            (
+                format!("{base_indentation}{}", self.indentation.get_unit()),
+                closes_block.then_some(base_indentation),
+            )
+        );
+    }
+
+    fn dedent_before_closing_bracket(&mut self) {
+        let Some(line) = self.buffer.lines.get(self.text_location.line_index) else {
+            return;
+        };
+        let before = line.substr(0..self.text_location.grapheme_index);
+
+        if !before.chars().all(char::is_whitespace) {
+            return;
+        }
+
+        let dedent_length = self.indentation.get_dedent_length(before);
+
+        if dedent_length == 0 {
+            return;
+        }
+
+        let start = Location {
+            line_index: self.text_location.line_index,
+            grapheme_index: self.text_location.grapheme_index.saturating_sub(dedent_length),
+        };
+
+        self.buffer.remove_text(start, self.text_location);
+        self.text_location = start;
+    }
+
+    pub fn toggle_auto_indent(&mut self) -> bool {
+        self.indentation.auto_indent = !self.indentation.auto_indent;
+
+        return self.indentation.auto_indent;
+    }
+
+    fn delete_previous(&mut self) {
+        if self.delete_empty_pair() || self.delete_indentation_unit() {
+            return;
+        }
+
+        if self.text_location.line_index != 0 || self.text_location.grapheme_index != 0 {
+            self.move_cursor(Move::Left);
+            self.buffer.remove_char(self.text_location);
+
+            self.set_needs_redraw(true);
+        }
+    }
+
+    fn delete_indentation_unit(&mut self) -> bool {
+        if self.indentation.hard_tabs || self.text_location.grapheme_index == 0 {
+            return false;
+        }
+
+        let Some(line) = self.buffer.lines.get(self.text_location.line_index) else {
+            return false;
+        };
+        let before = line.substr(0..self.text_location.grapheme_index);
+
+        if !before.chars().all(|character| { return character == ' '; }) {
+            return false;
+        }
+
+        let start = Location {
+            line_index: self.text_location.line_index,
+            grapheme_index: self.text_location.grapheme_index.saturating_sub(self.indentation.get_dedent_length(before)),
+        };
+
+        self.buffer.remove_text(start, self.text_location);
+        self.text_location = start;
+
+        self.scroll_text_location_into_view();
+        self.set_needs_redraw(true);
+
+        return true;
+    }
+
+    fn delete_next(&mut self) {
+        self.buffer.remove_char(self.text_location);
+
+        self.set_needs_redraw(true);
+    }
+
+    fn delete_word_previous(&mut self) {
+        let start = self.buffer.get_previous_word_location(self.text_location, self.subword_mode);
+
+        self.buffer.remove_text(start, self.text_location);
+        self.text_location = start;
+
+        self.scroll_text_location_into_view();
+        self.set_needs_redraw(true);
+    }
+
+    fn delete_word_next(&mut self) {
+        let end = self.buffer.get_next_word_location(self.text_location, self.subword_mode);
+
+        self.buffer.remove_text(self.text_location, end);
+
+        self.set_needs_redraw(true);
+    }
+
+    pub fn toggle_subword_mode(&mut self) -> bool {
+        self.subword_mode = !self.subword_mode;
+
+        return self.subword_mode;
+    }
+
+    pub fn copy(&mut self) -> ClipboardEntry {
+        let entry = if let Some(block) = self.block_selection {
+            self.get_block_entry(block)
+        } else {
+            self.get_selected_entry()
+        };
+
+        self.registers.kill_ring.push(entry.clone());
+
+        return entry;
+    }
+
+    fn get_block_entry(&self, block: BlockSelection) -> ClipboardEntry {
+        let top = min(block.anchor.row, block.head.row);
+        let bottom = max(block.anchor.row, block.head.row);
+        let left = min(block.anchor.column, block.head.column);
+        let right = max(block.anchor.column, block.head.column);
+        let text = (top..=bottom)
+            .map(
+                |line_index| {
+                    return self.buffer.lines.get(line_index).map_or_else(
+                        String::new,
+                        |line| {
+                            return self.buffer.get_text(
+                                Location {
+                                    line_index,
+                                    grapheme_index: line.grapheme_index_at_column(left, self.indentation.tab_width),
+                                },
+                                Location {
+                                    line_index,
+                                    grapheme_index: line.grapheme_index_from_column(right, self.indentation.tab_width),
+                                },
+                            );
+                        }
+                    );
+                }
+            )
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        return ClipboardEntry {
+            text,
+            linewise: false,
+            blockwise: true,
+        };
+    }
+
+    fn get_selected_entry(&self) -> ClipboardEntry {
+        if self.cursors.is_empty() {
+            return self.get_cursor_entry(&self.get_primary_cursor());
+        }
+
+        let entries: Vec<ClipboardEntry> = self
+            .get_cursors()
+            .iter()
+            .map(
+                |cursor| {
+                    return self.get_cursor_entry(cursor);
+                }
+            )
+            .collect();
+        let linewise = entries
+            .iter()
+            .all(
+                |entry| {
+                    return entry.linewise;
+                }
+            );
+        let text = entries
+            .iter()
+            .map(
+                |entry| {
+                    if entry.linewise {
+                        return entry.text.strip_suffix('\n').unwrap_or(&entry.text);
+                    }
+
+                    return entry.text.as_str();
+                }
+            )
+            .collect::<Vec<&str>>()
+            .join("\n");
+
+        return ClipboardEntry {
+            text: if linewise {
+                format!("{text}\n")
+            } else {
+                text
+            },
+            linewise,
+            blockwise: false,
+        };
+    }
+
+    fn get_cursor_entry(&self, cursor: &Cursor) -> ClipboardEntry {
+        if let Some((start, end)) = cursor.get_selection_range() {
+            return ClipboardEntry {
+                text: self.buffer.get_text(start, end),
+                linewise: false,
+                blockwise: false,
+            };
+        }
+
+        if self.block_selection.is_some() {
+            return ClipboardEntry::default();
+        }
+
+        let line = self.buffer.lines.get(cursor.location.line_index).map_or_else(String::new, Line::to_string);
+
+        return ClipboardEntry {
+            text: format!("{line}\n"),
+            linewise: true,
+            blockwise: false,
+        };
+    }
+
+    pub fn cut(&mut self) -> ClipboardEntry {
+        let entry = self.copy();
+        let location_before = self.text_location;
+
+        self.for_each_cursor(
+            |view, _| {
+                if !view.delete_selection() && view.text_location.line_index < view.buffer.height() {
+                    view.delete_lines();
+                }
+            }
+        );
+
+        self.commit_edit(location_before);
+
+        return entry;
+    }
+
+    pub fn paste(&mut self, entry: &ClipboardEntry) {
+        if self.registers.kill_ring.head() != Some(entry) {
+            self.registers.kill_ring.push(entry.clone());
+        }
+
+        self.registers.kill_ring.reset_yank_index();
+        self.paste_entry(entry);
+    }
+
+    pub fn paste_from_register(&mut self, name: char) -> bool {
+        if let Some(entry) = self.registers.get(name).cloned() {
+            self.paste_entry(&entry);
+
+            return true;
+        }
+
+        return false;
+    }
+
+    fn paste_entry(&mut self, entry: &ClipboardEntry) {
+        let location_before = self.text_location;
+
+        if entry.blockwise && self.cursors.is_empty() {
+            self.delete_selection();
+            self.insert_block(entry);
+            self.commit_edit(location_before);
+
+            return;
+        }
+
+        let parts = Self::split_entry(entry, self.cursors.len().saturating_add(1));
+
+        self.for_each_cursor(
+            |view, index| {
+                view.delete_selection();
+                view.insert_entry(parts.get(index).unwrap_or(entry));
+            }
+        );
+
+        if !self.cursors.is_empty() {
+            self.yank_range = None;
+        }
+
+        self.commit_edit(location_before);
+    }
+
+    fn insert_block(&mut self, entry: &ClipboardEntry) {
+        let Position {
+            column,
+            row,
+        } = self.text_location_to_position();
+        let mut end = self.text_location;
+
+        for (line_offset, text) in entry.text.split('\n').enumerate() {
+            let line_index = row.saturating_add(line_offset);
+
+            if line_index >= self.buffer.height() {
+                self.buffer.insert_text("\n", self.buffer.end_location());
+            }
+
+            let (grapheme_index, padding) = self.buffer.lines.get(line_index).map_or(
+                (0, column),
+                |line| {
+                    return (
+                        line.grapheme_index_at_column(column, self.indentation.tab_width),
+                        column.saturating_sub(line.width(self.indentation.tab_width)),
+                    );
+                }
+            );
+            let start = Location {
+                line_index,
+                grapheme_index,
+            };
+
+            if text.is_empty() {
+                continue;
+            }
+
+            end = self.buffer.insert_text(&format!("{}{text}", " ".repeat(padding)), start);
+        }
+
+        self.text_location = end;
+        self.yank_range = None;
+
+        self.scroll_text_location_into_view();
+        self.set_needs_redraw(true);
+    }
+
+    fn split_entry(entry: &ClipboardEntry, cursors_count: usize) -> Vec<ClipboardEntry> {
+        if cursors_count < 2 {
+            return Vec::new();
+        }
+
+        let text = if entry.linewise {
+            entry.text.strip_suffix('\n').unwrap_or(&entry.text)
+        } else {
+            &entry.text
+        };
+        let parts: Vec<ClipboardEntry> = text
+            .split('\n')
+            .map(
+                |part| {
+                    return ClipboardEntry {
+                        text: if entry.linewise {
+                            format!("{part}\n")
+                        } else {
+                            String::from(part)
+                        },
+                        linewise: entry.linewise,
+                        blockwise: false,
+                    };
+                }
+            )
+            .collect();
+
+        if parts.len() != cursors_count {
+            return Vec::new();
+        }
+
+        return parts;
+    }
+
+    pub fn copy_to_register(&mut self, name: char) {
+        let entry = self.get_selected_entry();
+
+        self.registers.set(name, entry);
+    }
+
+    pub fn yank_pop(&mut self) -> bool {
+        let Some((start, end)) = self.yank_range else {
+            return false;
+        };
+        let Some(entry) = self.registers.kill_ring.rotate().cloned() else {
+            return false;
+        };
+        let location_before = self.text_location;
+
+        self.buffer.remove_text(start, end);
+        self.text_location = start;
+        self.insert_entry(&entry);
+        self.commit_edit(location_before);
+
+        return true;
+    }
+
+    pub const fn get_registers(&self) -> &Registers {
+        return &self.registers;
+    }
+
+    fn insert_entry(&mut self, entry: &ClipboardEntry) {
+        let start = if entry.linewise {
+            Location {
+                line_index: self.text_location.line_index,
+                grapheme_index: 0,
+            }
+        } else {
+            self.text_location
+        };
+        let end = self.buffer.insert_text(&entry.text, start);
+
+        if entry.linewise {
+            self.text_location.line_index = end.line_index;
+        } else {
+            self.text_location = end;
+        }
+
+        self.yank_range = Some((start, end));
+
+        self.snap_to_valid_line();
+        self.snap_to_valid_grapheme();
+        self.scroll_text_location_into_view();
+        self.set_needs_redraw(true);
+    }
+
+    pub fn kill_line(&mut self, append: bool) -> ClipboardEntry {
+        if let Some((start, end)) = self.get_selection_range() {
+            return self.kill(start, end, append, false);
+        }
+
+        let line_length = self.buffer.lines.get(self.text_location.line_index).map_or(0, Line::grapheme_count);
+        let end = if self.text_location.grapheme_index < line_length {
+            Location {
+                line_index: self.text_location.line_index,
+                grapheme_index: line_length,
+            }
+        } else {
+            Location {
+                line_index: self.text_location.line_index.saturating_add(1),
+                grapheme_index: 0,
+            }
+        };
+
+        return self.kill(self.text_location, end, append, false);
+    }
+
+    pub fn kill_word_forward(&mut self, append: bool) -> ClipboardEntry {
+        let end = self.buffer.get_next_word_location(self.text_location, self.subword_mode);
+
+        return self.kill(self.text_location, end, append, false);
+    }
+
+    pub fn kill_word_backward(&mut self, append: bool) -> ClipboardEntry {
+        let start = self.buffer.get_previous_word_location(self.text_location, self.subword_mode);
+
+        return self.kill(start, self.text_location, append, true);
+    }
+
+    fn kill(&mut self, start: Location, end: Location, append: bool, backward: bool) -> ClipboardEntry {
+        let location_before = self.text_location;
+        let text = self.buffer.remove_text(start, end);
+
+        self.collapse_cursors();
+        self.selection_anchor = None;
+        self.text_location = start;
+        self.commit_edit(location_before);
+        self.scroll_text_location_into_view();
+        self.set_needs_redraw(true);
+
+        return self.registers.kill(&text, append, backward);
+    }
+
+    fn get_selection_range(&self) -> Option<(Location, Location)> {
+        return self.get_primary_cursor().get_selection_range();
+    }
+
+    fn get_line_highlights(&self, line_index: usize, bracket_pair: Option<(Location, Location)>) -> Vec<Highlight> {
+        let mut highlights: Vec<Highlight> = self
+            .get_cursors()
+            .iter()
+            .filter_map(
+                |cursor| {
+                    return self.get_line_selection(cursor, line_index);
+                }
+            )
+            .map(
+                |range| {
+                    return Highlight {
+                        annotation_type: AnnotationType::Selection,
+                        range,
+                    };
+                }
+            )
+            .collect();
+
+        if let Some((bracket, matching_bracket)) = bracket_pair {
+            for location in [bracket, matching_bracket] {
+                if location.line_index == line_index {
+                    highlights.push(
+                        Highlight {
+                            annotation_type: AnnotationType::MatchingBracket,
+                            range: location.grapheme_index..location.grapheme_index.saturating_add(1),
+                        }
+                    );
+                }
+            }
+        }
+
+        for cursor in &self.cursors {
+            if cursor.location.line_index == line_index {
+                highlights.push(
+                    Highlight {
+                        annotation_type: AnnotationType::Cursor,
+                        range: cursor.location.grapheme_index..cursor.location.grapheme_index.saturating_add(1),
+                    }
+                );
+            }
+        }
+
+        return highlights;
+    }
+
+    fn get_line_selection(&self, cursor: &Cursor, line_index: usize) -> Option<Range<usize>> {
+        let (start, end) = cursor.get_selection_range()?;
+
+        if line_index < start.line_index || line_index > end.line_index {
+            return None;
         }
 
-        self.scroll_text_location_into_view();
+        let from = if line_index == start.line_index {
+            start.grapheme_index
+        } else {
+            0
+        };
+        let to = if line_index == end.line_index {
+            end.grapheme_index
+        } else {
+            self.buffer.lines.get(line_index).map_or(0, Line::grapheme_count)
+        };
+
+        return Some(from..to);
     }
 
-    pub fn load(&mut self, file: &str) -> Result<(), Error> {
-        self.buffer = Buffer::load(file)?;
-        self.set_needs_redraw(true);
-
-        return Ok(());
+    fn clear_selection(&mut self) {
+        if self.selection_anchor.take().is_some() {
+            self.set_needs_redraw(true);
+        }
     }
 
-    pub const fn is_file_loaded(&self) -> bool {
-        return self.buffer.is_file_loaded();
-    }
+    fn get_selected_lines(&self) -> RangeInclusive<usize> {
+        let Some((start, end)) = self.get_selection_range() else {
+            return self.text_location.line_index..=self.text_location.line_index;
+        };
 
-    pub fn save(&mut self) -> Result<(), Error> {
-        return self.buffer.save();
+        if end.grapheme_index == 0 && end.line_index > start.line_index {
+            return start.line_index..=end.line_index.saturating_sub(1);
+        }
+
+        return start.line_index..=end.line_index;
     }
 
-    pub fn save_as(&mut self, file_name: &str) -> Result<(), Error> {
-        return self.buffer.save_as(file_name);
+    fn shift_selection_lines(&mut self, by: isize) {
+        self.text_location.line_index = self.text_location.line_index.saturating_add_signed(by);
+
+        if let Some(anchor) = &mut self.selection_anchor {
+            anchor.line_index = anchor.line_index.saturating_add_signed(by);
+        }
+
+        self.scroll_text_location_into_view();
+        self.set_needs_redraw(true);
     }
 
-    pub fn get_cursor_position(&self) -> Position {
-        return self.text_location_to_position().saturating_sub(self.scroll_offset);
+    fn duplicate_lines(&mut self) {
+        let lines_count = self.buffer.duplicate_lines(self.get_selected_lines());
+
+        self.shift_selection_lines(lines_count.try_into().unwrap_or(0));
     }
 
-    fn center_text_location(&mut self) {
-        let Size {
-            width,
-            height
-        } = self.size;
-        let Position {
-            column,
-            row
-        } = self.text_location_to_position();
+    fn apply_line_operation(&mut self, operation: LineOperation) {
+        let has_selection = self.get_selection_range().is_some();
+        let line_indices = if has_selection {
+            self.get_selected_lines()
+        } else {
+            0..=self.buffer.height().saturating_sub(1)
+        };
 
-        let horizontal_mid = width.div_ceil(2);
-        let vertical_mid = height.div_ceil(2);
+        let lines_count = self.buffer.apply_line_operation(line_indices.clone(), operation);
 
-        self.scroll_offset.column = column.saturating_sub(horizontal_mid);
-        self.scroll_offset.row = row.saturating_sub(vertical_mid);
+        if lines_count == 0 {
+            return;
+        }
 
-        self.set_needs_redraw(true);
-    }
+        if has_selection {
+            let last_line_index = line_indices.start().saturating_add(lines_count).saturating_sub(1);
 
-    fn text_location_to_position(&self) -> Position {
-        let column = self
-            .buffer
-            .lines
-            .get(
-                self.text_location.line_index
-            )
-            .map_or(
-                0,
-                |line| {
-                    return line.width_until(self.text_location.grapheme_index);
+            self.selection_anchor = Some(
+                Location {
+                    line_index: *line_indices.start(),
+                    grapheme_index: 0,
                 }
             );
-        let row = self.text_location.line_index;
+            self.text_location = Location {
+                line_index: last_line_index,
+                grapheme_index: self.buffer.lines.get(last_line_index).map_or(0, Line::grapheme_count),
+            };
+        }
 
-        return Position {
-            column,
-            row,
-        };
+        self.snap_to_valid_line();
+        self.snap_to_valid_grapheme();
+        self.scroll_text_location_into_view();
+        self.set_needs_redraw(true);
     }
 
-    fn move_up(&mut self, step: usize) {
-        self.text_location.line_index = self.text_location.line_index.saturating_sub(step);
-        self.snap_to_valid_grapheme();
+    fn increment_numbers(&mut self, delta: i64, sequence: bool) {
+        let Some((start, _)) = self.get_selection_range() else {
+            let line_index = self.text_location.line_index;
+            let Some((range, number)) = self
+                .buffer
+                .lines
+                .get(line_index)
+                .and_then(
+                    |line| {
+                        return line.add_to_number(self.text_location.grapheme_index, delta);
+                    }
+                ) else {
+                return;
+            };
+
+            let end = self.replace_number(line_index, range, &number);
+
+            self.text_location = Location {
+                line_index,
+                grapheme_index: end.grapheme_index.saturating_sub(1),
+            };
+
+            self.scroll_text_location_into_view();
+            self.set_needs_redraw(true);
+
+            return;
+        };
+        let pending_changes_count = self.buffer.get_pending_changes().len();
+        let mut step: i64 = 1;
+
+        for line_index in self.get_selected_lines() {
+            let from = if line_index == start.line_index {
+                start.grapheme_index
+            } else {
+                0
+            };
+            let Some((range, number)) = self
+                .buffer
+                .lines
+                .get(line_index)
+                .and_then(
+                    |line| {
+                        return line.add_to_number(from, delta.saturating_mul(step));
+                    }
+                ) else {
+                continue;
+            };
+
+            self.replace_number(line_index, range, &number);
+
+            if sequence {
+                step = step.saturating_add(1);
+            }
+        }
+
+        self.transform_primary_cursor(pending_changes_count);
     }
 
-    fn move_down(&mut self, step: usize) {
-        self.text_location.line_index = self.text_location.line_index.saturating_add(step);
-        self.snap_to_valid_grapheme();
-        self.snap_to_valid_line();
+    fn replace_number(&mut self, line_index: usize, range: Range<usize>, number: &str) -> Location {
+        let start = Location {
+            line_index,
+            grapheme_index: range.start,
+        };
+
+        self.buffer.remove_text(
+            start,
+            Location {
+                line_index,
+                grapheme_index: range.end,
+            },
+        );
+
+        return self.buffer.insert_text(number, start);
     }
 
-    fn move_right(&mut self) {
-        let line_width = self.buffer.lines.get(self.text_location.line_index).map_or(0, Line::grapheme_count);
-        if self.text_location.grapheme_index < line_width {
-            self.text_location.grapheme_index += 1;
-        } else {
-            self.move_to_start_of_line();
-            self.move_down(1);
+    fn get_text_width(&self) -> usize {
+        if let Some(text_width) = self.text_width {
+            return text_width;
         }
-    }
 
-    fn move_left(&mut self) {
-        if self.text_location.grapheme_index > 0 {
-            self.text_location.grapheme_index -= 1;
-        } else if self.text_location.line_index > 0 {
-            self.move_up(1);
-            self.move_to_end_of_line();
+        let is_commit_message = self
+            .buffer
+            .file_info
+            .get_path()
+            .and_then(Path::file_name)
+            .is_some_and(
+                |file_name| {
+                    return file_name == "COMMIT_EDITMSG";
+                }
+            );
+
+        if is_commit_message {
+            return COMMIT_MESSAGE_TEXT_WIDTH;
+        } else {
+            return DEFAULT_TEXT_WIDTH;
         }
     }
 
-    fn move_to_start_of_line(&mut self) {
-        self.text_location.grapheme_index = 0;
-    }
+    fn get_paragraph_lines(&self) -> Option<RangeInclusive<usize>> {
+        let line_index = self.text_location.line_index;
+        let line = self.buffer.lines.get(line_index)?.to_string();
 
-    fn move_to_end_of_line(&mut self) {
-        self.text_location.grapheme_index = self.buffer.lines.get(self.text_location.line_index).map_or(0, Line::grapheme_count);
-    }
+        if Reflower::is_blank(&line) {
+            return None;
+        }
 
-    fn snap_to_valid_grapheme(&mut self) {
-        self.text_location.grapheme_index = self.buffer.lines.get(self.text_location.line_index).map_or(0, |line| { min(line.grapheme_count(), self.text_location.grapheme_index) });
-    }
+        let marker = Reflower::get_prefix(&line).trim().to_string();
+        let belongs_to_paragraph = |line_index: usize| {
+            return self.buffer.lines.get(line_index).is_some_and(
+                |line| {
+                    let line = line.to_string();
 
-    fn snap_to_valid_line(&mut self) {
-        self.text_location.line_index = min(self.text_location.line_index, self.buffer.height());
-    }
+                    return !Reflower::is_blank(&line) && Reflower::get_prefix(&line).trim() == marker;
+                }
+            );
+        };
+        let mut first_line_index = line_index;
+        let mut last_line_index = line_index;
 
-    fn scroll_horizontally(&mut self, to_where: Column) {
-        let Size {
-            width,
-            height: _
-        } = self.size;
-        let offset_changed = if to_where < self.scroll_offset.column {
-            self.scroll_offset.column = to_where;
+        while first_line_index > 0 && belongs_to_paragraph(first_line_index.saturating_sub(1)) {
+            first_line_index = first_line_index.saturating_sub(1);
+        }
 
-            true
-        } else if to_where >= self.scroll_offset.column.saturating_add(width) {
-            self.scroll_offset.column = to_where.saturating_sub(width).saturating_add(1);
+        while belongs_to_paragraph(last_line_index.saturating_add(1)) {
+            last_line_index = last_line_index.saturating_add(1);
+        }
 
-            true
+        return Some(first_line_index..=last_line_index);
+    }
+
+    fn reflow(&mut self) {
+        let line_indices = if self.get_selection_range().is_some() {
+            self.get_selected_lines()
+        } else if let Some(line_indices) = self.get_paragraph_lines() {
+            line_indices
         } else {
-            false
+            return;
         };
+        let lines = self.buffer.get_lines(line_indices.clone());
+        let reflowed_lines = Reflower::reflow(&lines, self.get_text_width(), self.indentation.tab_width);
 
-        if offset_changed {
-            self.set_needs_redraw(true);
+        if reflowed_lines == lines {
+            return;
         }
-    }
 
-    fn scroll_vertically(&mut self, to_where: Row) {
-        let Size {
-            width: _,
-            height
-        } = self.size;
-        let offset_changed = if to_where < self.scroll_offset.row {
-            self.scroll_offset.row = to_where;
+        self.buffer.replace_lines(line_indices.clone(), &reflowed_lines);
 
-            true
-        } else if to_where >= self.scroll_offset.row.saturating_add(height) {
-            self.scroll_offset.row = to_where.saturating_sub(height).saturating_add(1);
+        let last_line_index = line_indices.start().saturating_add(reflowed_lines.len()).saturating_sub(1);
 
-            true
-        } else {
-            false
+        self.selection_anchor = None;
+        self.text_location = Location {
+            line_index: last_line_index,
+            grapheme_index: self.buffer.lines.get(last_line_index).map_or(0, Line::grapheme_count),
         };
 
-        if offset_changed {
-            self.set_needs_redraw(true);
+        self.scroll_text_location_into_view();
+        self.set_needs_redraw(true);
+    }
+
+    fn delete_lines(&mut self) {
+        let first_line_index = *self.get_selected_lines().start();
+
+        self.buffer.delete_lines(self.get_selected_lines());
+        self.selection_anchor = None;
+        self.text_location.line_index = min(first_line_index, self.buffer.height().saturating_sub(1));
+
+        self.snap_to_valid_grapheme();
+        self.scroll_text_location_into_view();
+        self.set_needs_redraw(true);
+    }
+
+    fn move_lines_up(&mut self) {
+        if self.buffer.move_lines_up(self.get_selected_lines()) {
+            self.shift_selection_lines(-1);
         }
     }
 
-    fn scroll_text_location_into_view(&mut self) {
-        let Position {
-            column,
-            row
-        } = self.text_location_to_position();
+    fn move_lines_down(&mut self) {
+        if self.buffer.move_lines_down(self.get_selected_lines()) {
+            self.shift_selection_lines(1);
+        }
+    }
 
-        self.scroll_horizontally(column);
-        self.scroll_vertically(row);
+    fn join_lines(&mut self) {
+        if let Some(location) = self.buffer.join_lines(self.get_selected_lines()) {
+            self.selection_anchor = None;
+            self.text_location = location;
+
+            self.scroll_text_location_into_view();
+            self.set_needs_redraw(true);
+        }
     }
 
-    fn insert_char(&mut self, character: char) {
-        let old_length = self.buffer.lines.get(self.text_location.line_index).map_or(0, Line::grapheme_count);
+    fn delete_selection(&mut self) -> bool {
+        if let Some((start, end)) = self.get_selection_range() {
+            self.buffer.remove_text(start, end);
+            self.text_location = start;
+            self.selection_anchor = None;
 
-        self.buffer.insert_char(
-            character,
-            self.text_location
-        );
+            self.scroll_text_location_into_view();
+            self.set_needs_redraw(true);
 
-        let new_length = self.buffer.lines.get(self.text_location.line_index).map_or(0, Line::grapheme_count);
+            return true;
+        }
 
-        let grapheme_delta = new_length.saturating_sub(old_length);
+        self.selection_anchor = None;
 
-        if grapheme_delta > 0 {
-            self.handle_move_command(Move::Right);
+        return false;
+    }
+
+    fn commit_edit(&mut self, location_before: Location) {
+        let changes = self.buffer.get_pending_changes().to_vec();
+
+        if !changes.is_empty() {
+            self.transform_locations(&changes);
+            self.change_list.push(self.text_location);
         }
 
-        self.set_needs_redraw(true);
+        self.buffer.commit_edit(location_before, self.text_location);
     }
 
-    fn insert_tab(&mut self) {
-        for _ in 0..4 {
-            self.insert_char(' ');
+    fn transform_locations(&mut self, changes: &[Change]) {
+        for change in changes {
+            self.jump_list.transform(change);
+            self.change_list.transform(change);
+            self.marks.transform(change);
         }
     }
 
-    fn insert_line(&mut self) {
-        self.buffer.insert_line(self.text_location);
-        self.handle_move_command(Move::Right);
+    fn undo(&mut self) {
+        self.overwritten.clear();
 
-        self.set_needs_redraw(true);
+        if let Some((location, changes)) = self.buffer.undo() {
+            self.transform_locations(&changes);
+            self.move_to_edited_location(location);
+        }
     }
 
-    fn delete_previous(&mut self) {
-        if self.text_location.line_index != 0 || self.text_location.grapheme_index != 0 {
-            self.handle_move_command(Move::Left);
-            self.buffer.remove_char(self.text_location);
+    fn redo(&mut self) {
+        self.overwritten.clear();
 
-            self.set_needs_redraw(true);
+        if let Some((location, changes)) = self.buffer.redo() {
+            self.transform_locations(&changes);
+            self.move_to_edited_location(location);
         }
     }
 
-    fn delete_next(&mut self) {
-        self.buffer.remove_char(self.text_location);
+    fn move_to_edited_location(&mut self, location: Location) {
+        self.text_location = location;
 
+        self.snap_to_valid_line();
+        self.snap_to_valid_grapheme();
+        self.scroll_text_location_into_view();
         self.set_needs_redraw(true);
     }
 
     pub fn enter_search(&mut self) {
+        self.collapse_cursors();
+        self.clear_selection();
         self.search_info = Some(
             SearchInfo {
                 previous_location: self.text_location,
@@ -331,7 +2694,9 @@ impl View {
     }
 
     pub fn exit_search(&mut self) {
-        self.search_info = None;
+        if let Some(search_info) = self.search_info.take() {
+            self.record_jump(search_info.previous_location);
+        }
 
         self.set_needs_redraw(true);
     }
@@ -466,6 +2831,8 @@ impl UIElement for View {
             height
         } = self.size;
         let final_row = row.saturating_add(height);
+        let bracket_pair = self.get_cached_bracket_pair();
+        let gutter_width = self.get_gutter_width();
 
         for current_line in row..final_row {
             let line_index = current_line
@@ -474,7 +2841,7 @@ impl UIElement for View {
 
             if let Some(line) = self.buffer.lines.get(line_index) {
                 let left = self.scroll_offset.column;
-                let right = self.scroll_offset.column.saturating_add(width);
+                let right = self.scroll_offset.column.saturating_add(width.saturating_sub(gutter_width));
 
                 let query = self
                     .search_info
@@ -485,10 +2852,29 @@ impl UIElement for View {
                         }
                     );
                 let selected_match = (self.text_location.line_index == line_index && query.is_some()).then_some(self.text_location.grapheme_index);
+                let highlights = self.get_line_highlights(line_index, bracket_pair);
+                let mut annotated_line = line.get_annotated_visible_substr(left..right, self.indentation.tab_width, query, selected_match, &highlights);
+
+                if gutter_width > 0 {
+                    let mark = self.marks.get_line_mark(line_index);
+
+                    annotated_line.replace(
+                        0,
+                        0,
+                        &format!(
+                            "{:gutter_width$}",
+                            mark.unwrap_or(' ')
+                        ),
+                    );
+
+                    if mark.is_some() {
+                        annotated_line.add_annotation(AnnotationType::Mark, 0, 1);
+                    }
+                }
 
                 Terminal::print_annotated_line(
                     current_line,
-                    &line.get_annotated_visible_substr(left..right, query, selected_match),
+                    &annotated_line,
                 )?;
             } else if current_line == height.div_ceil(3) && self.buffer.is_empty() {
                 Self::render_line(
@@ -505,4 +2891,126 @@ impl UIElement for View {
 
         return Ok(());
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(line_index: usize, grapheme_index: usize) -> Location {
+        return Location {
+            grapheme_index,
+            line_index,
+        };
+    }
+
+    fn to_pair(location: Option<Location>) -> Option<(usize, usize)> {
+        return location.map(
+            |location| {
+                return (location.line_index, location.grapheme_index);
+            }
+        );
+    }
+
+    fn type_text(view: &mut View, text: &str) {
+        for character in text.chars() {
+            if character == '\n' {
+                view.handle_edit_command(Edit::InsertLine);
+            } else {
+                view.handle_edit_command(Edit::InsertCharacter(character));
+            }
+        }
+    }
+
+    #[test]
+    fn marks_and_location_lists_follow_edits() {
+        let mut view = View::default();
+
+        type_text(&mut view, "alpha\nbeta\ngamma");
+        view.set_mark_at('a', at(1, 2));
+        view.set_mark_at('b', at(2, 3));
+        view.set_mark_at('c', at(0, 1));
+        view.jump_list.push(at(2, 4));
+
+        view.text_location = at(1, 0);
+        type_text(&mut view, "XY");
+        view.text_location = at(1, 6);
+        type_text(&mut view, "!");
+
+        assert_eq!(to_pair(view.marks.get('a')), Some((1, 4)));
+        assert_eq!(to_pair(view.marks.get('b')), Some((2, 3)));
+        assert_eq!(to_pair(view.marks.get('c')), Some((0, 1)));
+
+        view.text_location = at(0, 0);
+        type_text(&mut view, "\n");
+
+        assert_eq!(to_pair(view.marks.get('a')), Some((2, 4)));
+        assert_eq!(to_pair(view.marks.get('b')), Some((3, 3)));
+        assert_eq!(to_pair(view.marks.get('c')), Some((1, 1)));
+        assert!(view.go_to_previous_change());
+        assert_eq!(to_pair(Some(view.text_location)), Some((2, 7)));
+        assert!(view.go_to_previous_change());
+        assert_eq!(to_pair(Some(view.text_location)), Some((3, 5)));
+
+        view.text_location = at(2, 0);
+        view.handle_edit_command(Edit::DeleteLine);
+
+        assert_eq!(view.buffer.lines.iter().map(Line::to_string).collect::<Vec<String>>(), ["", "alpha", "gamma"]);
+        assert_eq!(to_pair(view.marks.get('a')), Some((2, 0)));
+        assert_eq!(to_pair(view.marks.get('b')), Some((2, 3)));
+        assert_eq!(to_pair(view.marks.get('c')), Some((1, 1)));
+
+        view.text_location = at(0, 0);
+        assert!(view.jump_back());
+        assert_eq!(to_pair(Some(view.text_location)), Some((2, 4)));
+    }
+
+    fn get_lines(view: &View) -> Vec<String> {
+        return view.buffer.lines.iter().map(Line::to_string).collect();
+    }
+
+    #[test]
+    fn auto_wrap_only_wraps_prose_files() {
+        for (file_name, expected_lines) in [("notes.md", vec!["one two", "three"]), ("main.rs", vec!["one two three"])] {
+            let mut view = View::default();
+
+            view.buffer.file_info = FileInfo::from(file_name);
+            view.auto_wrap = true;
+            view.text_width = Some(10);
+            type_text(&mut view, "one two three");
+
+            assert_eq!(get_lines(&view), expected_lines, "{file_name}");
+        }
+
+        let mut view = View::default();
+
+        view.buffer.file_info = FileInfo::from("notes.md");
+        view.text_width = Some(10);
+        type_text(&mut view, "one two three");
+
+        assert_eq!(get_lines(&view), ["one two three"]);
+    }
+
+    #[test]
+    fn bracket_pair_follows_the_cursor_and_edits() {
+        let mut view = View::default();
+        let get_pair = |view: &mut View| {
+            return view.get_cached_bracket_pair().map(
+                |(bracket, matching_bracket)| {
+                    return (to_pair(Some(bracket)), to_pair(Some(matching_bracket)));
+                }
+            );
+        };
+
+        type_text(&mut view, "(a)");
+        assert_eq!(get_pair(&mut view), Some((Some((0, 2)), Some((0, 0)))));
+        assert_eq!(get_pair(&mut view), Some((Some((0, 2)), Some((0, 0)))));
+
+        view.text_location = at(0, 0);
+        type_text(&mut view, "x");
+        assert_eq!(get_pair(&mut view), Some((Some((0, 1)), Some((0, 3)))));
+
+        view.text_location = at(0, 0);
+        assert_eq!(get_pair(&mut view), None);
+    }
+}
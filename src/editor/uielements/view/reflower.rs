@@ -0,0 +1,204 @@
+use super::Line;
+
+pub const DEFAULT_TEXT_WIDTH: usize = 80;
+pub const COMMIT_MESSAGE_TEXT_WIDTH: usize = 72;
+pub const PROSE_EXTENSIONS: [&str; 7] = [
+    "md",
+    "markdown",
+    "txt",
+    "text",
+    "rst",
+    "adoc",
+    "org",
+];
+
+const COMMENT_MARKERS: [&str; 5] = [
+    "///",
+    "//!",
+    "//",
+    "#",
+    ">",
+];
+
+pub struct Reflower;
+
+impl Reflower {
+    pub fn get_prefix(line: &str) -> &str {
+        let mut length = line.len().saturating_sub(line.trim_start().len());
+
+        if let Some(marker) = COMMENT_MARKERS.iter().find(|marker| { return line[length..].starts_with(*marker); }) {
+            length = length.saturating_add(marker.len());
+
+            loop {
+                let rest = &line[length..];
+                let spaces_length = rest.len().saturating_sub(rest.trim_start_matches(' ').len());
+
+                length = length.saturating_add(spaces_length);
+
+                if *marker == ">" && line[length..].starts_with('>') {
+                    length = length.saturating_add(1);
+                } else {
+                    break;
+                }
+            }
+        }
+
+        return &line[..length];
+    }
+
+    pub fn get_common_prefix(lines: &[&str]) -> String {
+        let prefixes = lines
+            .iter()
+            .map(
+                |line| {
+                    return Self::get_prefix(line);
+                }
+            )
+            .collect::<Vec<&str>>();
+        let Some(first_prefix) = prefixes.first() else {
+            return String::new();
+        };
+        let mut common_length = first_prefix.len();
+
+        for prefix in &prefixes[1..] {
+            common_length = first_prefix
+                .char_indices()
+                .zip(prefix.chars())
+                .take_while(
+                    |((_, first_character), character)| {
+                        return first_character == character;
+                    }
+                )
+                .last()
+                .map_or(
+                    0,
+                    |((byte_index, character), _)| {
+                        return byte_index.saturating_add(character.len_utf8());
+                    }
+                )
+                .min(common_length);
+        }
+
+        let common = &first_prefix[..common_length];
+        let rest = &first_prefix[common_length..];
+
+        if rest.chars().all(char::is_whitespace) {
+            return String::from(*first_prefix);
+        }
+
+        return String::from(common);
+    }
+
+    pub fn is_blank(line: &str) -> bool {
+        return line[Self::get_prefix(line).len()..].trim().is_empty();
+    }
+
+    pub fn reflow<T: AsRef<str>>(lines: &[T], width: usize, tab_width: usize) -> Vec<String> {
+        let mut result = Vec::new();
+        let mut paragraph = Vec::new();
+
+        for line in lines {
+            let line = line.as_ref();
+
+            if Self::is_blank(line) {
+                result.append(&mut Self::reflow_paragraph(&paragraph, width, tab_width));
+                result.push(String::from(line));
+                paragraph.clear();
+            } else {
+                paragraph.push(line);
+            }
+        }
+
+        result.append(&mut Self::reflow_paragraph(&paragraph, width, tab_width));
+
+        return result;
+    }
+
+    fn reflow_paragraph(lines: &[&str], width: usize, tab_width: usize) -> Vec<String> {
+        if lines.is_empty() {
+            return Vec::new();
+        }
+
+        let prefix = Self::get_common_prefix(lines);
+        let common_length = prefix.trim_end().len();
+        let words = lines
+            .iter()
+            .flat_map(
+                |line| {
+                    return line[common_length..].split_whitespace();
+                }
+            )
+            .collect::<Vec<&str>>();
+
+        return Self::wrap_words(&prefix, &words, width, tab_width);
+    }
+
+    pub fn wrap_words(prefix: &str, words: &[&str], width: usize, tab_width: usize) -> Vec<String> {
+        let mut result = Vec::new();
+        let mut current = String::from(prefix);
+        let mut has_word = false;
+
+        for word in words {
+            let candidate = if has_word {
+                format!("{current} {word}")
+            } else {
+                format!("{current}{word}")
+            };
+
+            if has_word && Line::from(&candidate).width(tab_width) > width {
+                result.push(current);
+                current = format!("{prefix}{word}");
+            } else {
+                current = candidate;
+            }
+
+            has_word = true;
+        }
+
+        result.push(current);
+
+        return result;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get_prefix_keeps_indentation_and_comment_markers() {
+        assert_eq!(Reflower::get_prefix("    // hello"), "    // ");
+        assert_eq!(Reflower::get_prefix("/// docs"), "/// ");
+        assert_eq!(Reflower::get_prefix("# note"), "# ");
+        assert_eq!(Reflower::get_prefix("> > quote"), "> > ");
+        assert_eq!(Reflower::get_prefix("  plain"), "  ");
+        assert_eq!(Reflower::get_prefix("plain"), "");
+    }
+
+    #[test]
+    fn get_common_prefix_uses_the_shared_part() {
+        assert_eq!(Reflower::get_common_prefix(&["// a", "//   b"]), "// ");
+        assert_eq!(Reflower::get_common_prefix(&["> > a", "> b"]), "> ");
+        assert_eq!(Reflower::get_common_prefix(&[]), "");
+    }
+
+    #[test]
+    fn is_blank_ignores_the_prefix() {
+        assert!(Reflower::is_blank(""));
+        assert!(Reflower::is_blank("  //  "));
+        assert!(!Reflower::is_blank("// text"));
+    }
+
+    #[test]
+    fn reflow_wraps_each_paragraph() {
+        assert_eq!(Reflower::reflow(&["one two", "three four five"], 10, 4), ["one two", "three four", "five"]);
+        assert_eq!(Reflower::reflow(&["a", "", "b", "c"], 80, 4), ["a", "", "b c"]);
+        assert_eq!(Reflower::reflow(&["// one two", "// three"], 80, 4), ["// one two three"]);
+    }
+
+    #[test]
+    fn wrap_words_keeps_long_words_whole() {
+        assert_eq!(Reflower::wrap_words("# ", &["abcdefghij", "k"], 5, 4), ["# abcdefghij", "# k"]);
+        assert_eq!(Reflower::wrap_words("", &[], 5, 4), [""]);
+    }
+}
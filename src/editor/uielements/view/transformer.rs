@@ -0,0 +1,359 @@
+use std::fmt::Write;
+use base64::{
+    engine::general_purpose::{
+        STANDARD,
+        STANDARD_NO_PAD,
+        URL_SAFE,
+        URL_SAFE_NO_PAD,
+    },
+    Engine,
+};
+use sha2::{
+    Digest,
+    Sha256,
+};
+use unicode_segmentation::UnicodeSegmentation;
+use super::Transform;
+
+pub struct Transformer;
+
+impl Transformer {
+    pub fn apply(transform: Transform, text: &str) -> Result<String, String> {
+        match transform {
+            Transform::UpperCase => {
+                return Ok(text.to_uppercase());
+            },
+            Transform::LowerCase => {
+                return Ok(text.to_lowercase());
+            },
+            Transform::TitleCase => {
+                return Ok(Self::to_title_case(text));
+            },
+            Transform::SwapCase => {
+                return Ok(Self::swap_case(text));
+            },
+            Transform::Base64Encode => {
+                return Ok(STANDARD.encode(text));
+            },
+            Transform::Base64Decode => {
+                return Self::base64_decode(text).ok_or_else(|| { return String::from("Invalid Base64"); });
+            },
+            Transform::UrlEncode => {
+                return Ok(Self::url_encode(text));
+            },
+            Transform::UrlDecode => {
+                return Self::url_decode(text).ok_or_else(|| { return String::from("Invalid URL encoding"); });
+            },
+            Transform::JsonEscape => {
+                return Ok(Self::json_escape(text));
+            },
+            Transform::JsonUnescape => {
+                return Self::json_unescape(text).ok_or_else(|| { return String::from("Invalid JSON string"); });
+            },
+            Transform::Sha256 => {
+                return Ok(format!("{:x}", Sha256::digest(text)));
+            },
+        }
+    }
+
+    pub const fn keeps_text(transform: Transform) -> bool {
+        return matches!(transform, Transform::Sha256);
+    }
+
+    fn to_title_case(text: &str) -> String {
+        let mut result = String::with_capacity(text.len());
+
+        for word in text.split_word_bounds() {
+            let mut characters = word.chars();
+
+            if let Some(first) = characters.next() {
+                result.push_str(&Self::to_title_char(first));
+                result.push_str(&characters.as_str().to_lowercase());
+            }
+        }
+
+        return result;
+    }
+
+    fn to_title_char(character: char) -> String {
+        match character {
+            'Ǆ' | 'ǅ' | 'ǆ' => {
+                return String::from('ǅ');
+            },
+            'Ǉ' | 'ǈ' | 'ǉ' => {
+                return String::from('ǈ');
+            },
+            'Ǌ' | 'ǋ' | 'ǌ' => {
+                return String::from('ǋ');
+            },
+            'Ǳ' | 'ǲ' | 'ǳ' => {
+                return String::from('ǲ');
+            },
+            _ => {
+                let upper = character.to_uppercase().collect::<String>();
+                let mut characters = upper.chars();
+                let first = characters.next().unwrap_or(character);
+
+                return format!(
+                    "{first}{}",
+                    characters.as_str().to_lowercase()
+                );
+            },
+        }
+    }
+
+    fn swap_case(text: &str) -> String {
+        return text
+            .chars()
+            .flat_map(
+                |character| {
+                    if character.is_uppercase() {
+                        return character.to_lowercase().collect::<Vec<char>>();
+                    } else if character.is_lowercase() {
+                        return character.to_uppercase().collect::<Vec<char>>();
+                    } else {
+                        return vec![character];
+                    }
+                }
+            )
+            .collect();
+    }
+
+    fn base64_decode(text: &str) -> Option<String> {
+        let compact = text
+            .chars()
+            .filter(
+                |character| {
+                    return !character.is_ascii_whitespace();
+                }
+            )
+            .collect::<String>();
+        let bytes = [STANDARD, STANDARD_NO_PAD, URL_SAFE, URL_SAFE_NO_PAD]
+            .iter()
+            .find_map(
+                |engine| {
+                    return engine.decode(&compact).ok();
+                }
+            )?;
+
+        return String::from_utf8(bytes).ok();
+    }
+
+    fn url_encode(text: &str) -> String {
+        let mut result = String::with_capacity(text.len());
+
+        for byte in text.bytes() {
+            if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+                result.push(char::from(byte));
+            } else {
+                let _ = write!(result, "%{byte:02X}");
+            }
+        }
+
+        return result;
+    }
+
+    fn url_decode(text: &str) -> Option<String> {
+        let mut bytes = Vec::with_capacity(text.len());
+        let mut remainder = text.as_bytes();
+
+        while let Some((&byte, rest)) = remainder.split_first() {
+            if byte == b'%' {
+                let code = rest.get(..2).filter(|code| { return code.iter().all(u8::is_ascii_hexdigit); })?;
+                let code = std::str::from_utf8(code).ok()?;
+
+                bytes.push(u8::from_str_radix(code, 16).ok()?);
+                remainder = &rest[2..];
+            } else {
+                bytes.push(byte);
+                remainder = rest;
+            }
+        }
+
+        return String::from_utf8(bytes).ok();
+    }
+
+    fn json_escape(text: &str) -> String {
+        let mut result = String::with_capacity(text.len());
+
+        for character in text.chars() {
+            match character {
+                '"' => {
+                    result.push_str("\\\"");
+                },
+                '\\' => {
+                    result.push_str("\\\\");
+                },
+                '\n' => {
+                    result.push_str("\\n");
+                },
+                '\r' => {
+                    result.push_str("\\r");
+                },
+                '\t' => {
+                    result.push_str("\\t");
+                },
+                '\u{8}' => {
+                    result.push_str("\\b");
+                },
+                '\u{c}' => {
+                    result.push_str("\\f");
+                },
+                _ if character.is_control() => {
+                    let _ = write!(result, "\\u{:04x}", u32::from(character));
+                },
+                _ => {
+                    result.push(character);
+                },
+            }
+        }
+
+        return result;
+    }
+
+    fn json_unescape(text: &str) -> Option<String> {
+        let mut result = String::with_capacity(text.len());
+        let mut characters = text.chars();
+
+        while let Some(character) = characters.next() {
+            if character != '\\' {
+                result.push(character);
+
+                continue;
+            }
+
+            match characters.next()? {
+                '"' => {
+                    result.push('"');
+                },
+                '\\' => {
+                    result.push('\\');
+                },
+                '/' => {
+                    result.push('/');
+                },
+                'n' => {
+                    result.push('\n');
+                },
+                'r' => {
+                    result.push('\r');
+                },
+                't' => {
+                    result.push('\t');
+                },
+                'b' => {
+                    result.push('\u{8}');
+                },
+                'f' => {
+                    result.push('\u{c}');
+                },
+                'u' => {
+                    let high = Self::read_json_code_unit(&mut characters)?;
+
+                    if (0xD800..0xDC00).contains(&high) {
+                        if characters.next()? != '\\' || characters.next()? != 'u' {
+                            return None;
+                        }
+
+                        let low = Self::read_json_code_unit(&mut characters)?;
+
+                        result.push(char::decode_utf16([high, low]).next()?.ok()?);
+                    } else {
+                        result.push(char::from_u32(u32::from(high))?);
+                    }
+                },
+                _ => {
+                    return None;
+                },
+            }
+        }
+
+        return Some(result);
+    }
+
+    fn read_json_code_unit(characters: &mut std::str::Chars) -> Option<u16> {
+        let code = characters.by_ref().take(4).collect::<String>();
+
+        if code.len() != 4 || !code.chars().all(|character| { return character.is_ascii_hexdigit(); }) {
+            return None;
+        }
+
+        return u16::from_str_radix(&code, 16).ok();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "Héllo \"wörld\"\n\t/ 100% ✓ 🦀 a+b=c?";
+
+    fn apply(transform: Transform, text: &str) -> Result<String, String> {
+        return Transformer::apply(transform, text);
+    }
+
+    #[test]
+    fn changes_case() {
+        assert_eq!(apply(Transform::UpperCase, "straße"), Ok(String::from("STRASSE")));
+        assert_eq!(apply(Transform::LowerCase, "ÀB"), Ok(String::from("àb")));
+        assert_eq!(apply(Transform::TitleCase, "hello WORLD ǆem"), Ok(String::from("Hello World ǅem")));
+        assert_eq!(apply(Transform::SwapCase, "aBc 1"), Ok(String::from("AbC 1")));
+    }
+
+    #[test]
+    fn base64_round_trips() {
+        let encoded = apply(Transform::Base64Encode, SAMPLE).unwrap();
+
+        assert_eq!(apply(Transform::Base64Encode, "hi?"), Ok(String::from("aGk/")));
+        assert_eq!(apply(Transform::Base64Decode, &encoded), Ok(String::from(SAMPLE)));
+        assert_eq!(apply(Transform::Base64Decode, "aGk_\n"), Ok(String::from("hi?")));
+        assert_eq!(apply(Transform::Base64Decode, "aGk"), Ok(String::from("hi")));
+    }
+
+    #[test]
+    fn base64_rejects_invalid_input() {
+        assert_eq!(apply(Transform::Base64Decode, "not base64!"), Err(String::from("Invalid Base64")));
+        assert_eq!(apply(Transform::Base64Decode, "/w=="), Err(String::from("Invalid Base64")));
+    }
+
+    #[test]
+    fn url_encoding_round_trips() {
+        let encoded = apply(Transform::UrlEncode, SAMPLE).unwrap();
+
+        assert_eq!(apply(Transform::UrlEncode, "a b/~"), Ok(String::from("a%20b%2F~")));
+        assert_eq!(apply(Transform::UrlDecode, &encoded), Ok(String::from(SAMPLE)));
+    }
+
+    #[test]
+    fn url_decoding_rejects_invalid_input() {
+        for text in ["100%", "%2", "%zz", "%FF"] {
+            assert_eq!(apply(Transform::UrlDecode, text), Err(String::from("Invalid URL encoding")), "{text}");
+        }
+    }
+
+    #[test]
+    fn json_round_trips() {
+        let escaped = apply(Transform::JsonEscape, "\"a\\b\"\n\u{1}").unwrap();
+
+        assert_eq!(escaped, "\\\"a\\\\b\\\"\\n\\u0001");
+        assert_eq!(apply(Transform::JsonUnescape, &apply(Transform::JsonEscape, SAMPLE).unwrap()), Ok(String::from(SAMPLE)));
+        assert_eq!(apply(Transform::JsonUnescape, "\\u00e9\\ud83e\\udd80\\/"), Ok(String::from("é🦀/")));
+    }
+
+    #[test]
+    fn json_unescaping_rejects_invalid_input() {
+        for text in ["\\", "\\x", "\\u12", "\\u12zz", "\\ud83e", "\\ud83ex"] {
+            assert_eq!(apply(Transform::JsonUnescape, text), Err(String::from("Invalid JSON string")), "{text}");
+        }
+    }
+
+    #[test]
+    fn sha256_hashes_the_text() {
+        assert_eq!(
+            apply(Transform::Sha256, "abc"),
+            Ok(String::from("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"))
+        );
+        assert!(Transformer::keeps_text(Transform::Sha256));
+        assert!(!Transformer::keeps_text(Transform::Base64Encode));
+    }
+}